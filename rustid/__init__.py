@@ -5,34 +5,280 @@ This module provides a drop-in replacement for Python's uuid module
 with significant performance improvements through Rust implementation.
 """
 
+import sys
+
 from .rustid import (
     UUID,
+    IdStream,
+    ArrowIdStream,
+    ArrowIdArray,
+    CompactIdGenerator,
+    Snowflake,
+    UuidArray,
+    IdFileReader,
+    BinaryIdReader,
+    IdSet,
+    ULID,
+    MonotonicUlidFactory,
+    TypeID,
+    PrefixRegistry,
     uuid1,
     uuid4,
+    uuid6,
     uuid7,
+    uuid3,
+    uuid5,
+    uuid8,
     uuid4_batch,
     uuid7_batch,
+    uuid7_batch_partition,
+    uuid7_monotonic,
+    uuid7_monotonic_batch,
+    uuid6_batch,
+    uuid3_batch,
+    uuid5_batch,
+    uuid7_with_str_batch,
+    uuid_bytes_batch,
+    uuid4_bytes_bulk,
+    uuid7_bytes_bulk,
+    uuid4_fill,
+    uuid7_fill,
+    write_index,
+    contains_batch,
+    uuid7_seq,
+    derive_uuid,
+    idempotency_key,
     short_id,
     short_id_batch,
+    base58_id,
+    decode_short_id,
     nano_id,
     nano_id_batch,
+    cuid2,
+    cuid2_batch,
+    arrow_stream,
+    uuid4_arrow,
+    uuid7_arrow,
+    generate_columns,
+    write_copy,
+    add_check_digit,
+    verify_check_digit,
+    coupon_code,
+    coupon_code_batch,
+    is_valid_coupon_code,
+    codename,
+    to_color,
+    to_identicon_seed,
+    distance,
+    prefix_match_len,
+    is_monotonic,
+    first_violation,
+    vectors,
+    recode,
+    encode,
+    decode,
+    alphabets,
+    upgrade_to_v7,
+    rewrap_random_as_v4,
+    normalize_batch,
+    parse,
+    is_valid,
+    parse_batch,
+    validate,
+    validate_batch,
+    detect_anomalies,
+    route_by_time,
+    time_window,
+    expiring_id,
+    expires_at,
+    is_expired,
+    region_id,
+    region_of,
+    read_ids,
+    sortable_key,
+    parse_sortable_key,
+    salted_key,
+    unsalt_key,
+    new_request_id,
+    current_request_id,
+    set_request_id,
+    reset_request_id,
+    trace_id,
+    span_id,
+    trace_id_batch,
+    span_id_batch,
+    should_sample,
+    uuid7_backfill_batch,
+    sample_keys,
+    simulate_collisions,
+    pseudonymize_file,
+    pseudonymize_ids,
+    rekey,
+    find_gaps,
+    intern_strings,
+    hash_batch,
+    set_hash_mode,
+    get_hash_mode,
+    set_strict_equality,
+    format_batch,
+    set_uppercase,
+    group,
+    ungroup,
+    self_check,
+    set_audit_hook,
+    pack_ids,
+    unpack_ids,
+    typeid,
+    ulid,
+    ulid_batch,
+    ulid_str_to_uuid_str,
+    uuid_str_to_ulid_str,
+    ulid_str_to_uuid_str_batch,
+    uuid_str_to_ulid_str_batch,
+    NAMESPACE_DNS,
+    NAMESPACE_URL,
+    NAMESPACE_OID,
+    NAMESPACE_X500,
 )
 
-# Compatibility with Python's uuid module
-from uuid import NAMESPACE_DNS, NAMESPACE_URL, NAMESPACE_OID, NAMESPACE_X500
+try:
+    from .rustid import write_parquet
+except ImportError:  # built without the "parquet" feature
+    write_parquet = None
+
+# Make ``import rustid.alphabets`` work for the extension submodule.
+sys.modules[__name__ + ".alphabets"] = alphabets
 
 __version__ = "0.0.1"
 __all__ = [
     "UUID",
+    "IdStream",
+    "ArrowIdStream",
+    "ArrowIdArray",
+    "CompactIdGenerator",
+    "Snowflake",
+    "UuidArray",
+    "IdFileReader",
+    "BinaryIdReader",
+    "IdSet",
+    "ULID",
+    "MonotonicUlidFactory",
+    "TypeID",
+    "PrefixRegistry",
     "uuid1", 
     "uuid4",
+    "uuid6",
     "uuid7",
+    "uuid3",
+    "uuid5",
+    "uuid8",
     "uuid4_batch",
     "uuid7_batch", 
+    "uuid7_batch_partition",
+    "uuid7_monotonic",
+    "uuid7_monotonic_batch",
+    "uuid6_batch",
+    "uuid3_batch",
+    "uuid5_batch",
+    "uuid7_with_str_batch",
+    "uuid_bytes_batch",
+    "uuid4_bytes_bulk",
+    "uuid7_bytes_bulk",
+    "uuid4_fill",
+    "uuid7_fill",
+    "write_index",
+    "contains_batch",
+    "uuid7_seq",
+    "derive_uuid",
+    "idempotency_key",
     "short_id",
     "short_id_batch",
+    "base58_id",
+    "decode_short_id",
     "nano_id",
     "nano_id_batch",
+    "cuid2",
+    "cuid2_batch",
+    "arrow_stream",
+    "uuid4_arrow",
+    "uuid7_arrow",
+    "generate_columns",
+    "write_copy",
+    "write_parquet",
+    "add_check_digit",
+    "verify_check_digit",
+    "coupon_code",
+    "coupon_code_batch",
+    "is_valid_coupon_code",
+    "codename",
+    "to_color",
+    "to_identicon_seed",
+    "distance",
+    "prefix_match_len",
+    "is_monotonic",
+    "first_violation",
+    "vectors",
+    "recode",
+    "encode",
+    "decode",
+    "alphabets",
+    "upgrade_to_v7",
+    "rewrap_random_as_v4",
+    "normalize_batch",
+    "parse",
+    "is_valid",
+    "parse_batch",
+    "validate",
+    "validate_batch",
+    "detect_anomalies",
+    "route_by_time",
+    "time_window",
+    "expiring_id",
+    "expires_at",
+    "is_expired",
+    "region_id",
+    "region_of",
+    "read_ids",
+    "sortable_key",
+    "parse_sortable_key",
+    "salted_key",
+    "unsalt_key",
+    "new_request_id",
+    "current_request_id",
+    "set_request_id",
+    "reset_request_id",
+    "trace_id",
+    "span_id",
+    "trace_id_batch",
+    "span_id_batch",
+    "should_sample",
+    "uuid7_backfill_batch",
+    "sample_keys",
+    "simulate_collisions",
+    "pseudonymize_file",
+    "pseudonymize_ids",
+    "rekey",
+    "find_gaps",
+    "intern_strings",
+    "hash_batch",
+    "set_hash_mode",
+    "get_hash_mode",
+    "set_strict_equality",
+    "format_batch",
+    "set_uppercase",
+    "group",
+    "ungroup",
+    "self_check",
+    "set_audit_hook",
+    "pack_ids",
+    "unpack_ids",
+    "typeid",
+    "ulid",
+    "ulid_batch",
+    "ulid_str_to_uuid_str",
+    "uuid_str_to_ulid_str",
+    "ulid_str_to_uuid_str_batch",
+    "uuid_str_to_ulid_str_batch",
     "NAMESPACE_DNS",
     "NAMESPACE_URL", 
     "NAMESPACE_OID",
@@ -0,0 +1,10 @@
+URL_SAFE: str
+ALPHANUMERIC: str
+BASE36: str
+BASE58: str
+CROCKFORD32: str
+HEX_LOWER: str
+HEX_UPPER: str
+NOLOOKALIKES: str
+NUMBERS: str
+SORTABLE64: str
@@ -1,32 +1,651 @@
-from typing import Optional, List
+import os
+from contextvars import Token
+from datetime import datetime, timedelta
+from ipaddress import IPv6Address
+from types import ModuleType
+from typing import IO, Any, Callable, Dict, Optional, List, Iterable, Iterator, Literal, Mapping, Sequence, Tuple, TypedDict, TypeVar, Union, overload
+
+_T = TypeVar("_T")
 
 class UUID:
-    def __init__(self, hex: Optional[str] = None, bytes: Optional[bytes] = None) -> None: ...
+    def __init__(
+        self,
+        hex: Optional[str] = None,
+        bytes: Optional[bytes] = None,
+        bytes_le: Optional[bytes] = None,
+        fields: Optional[Tuple[int, int, int, int, int, int]] = None,
+        int: Optional[int] = None,
+        version: Optional[int] = None,
+        *,
+        urn: Optional[str] = None,
+    ) -> None: ...
     @property
     def hex(self) -> str: ...
     @property
     def bytes(self) -> bytes: ...
     @property
+    def bytes_le(self) -> bytes: ...
+    @property
+    def fields(self) -> Tuple[int, int, int, int, int, int]: ...
+    @property
+    def time_low(self) -> int: ...
+    @property
+    def time_mid(self) -> int: ...
+    @property
+    def time_hi_version(self) -> int: ...
+    @property
+    def clock_seq_hi_variant(self) -> int: ...
+    @property
+    def clock_seq_low(self) -> int: ...
+    @property
+    def clock_seq(self) -> int: ...
+    @property
+    def node(self) -> int: ...
+    @property
+    def time(self) -> int: ...
+    @property
+    def timestamp(self) -> float: ...
+    @property
+    def timestamp_ms(self) -> int: ...
+    @property
+    def datetime(self) -> datetime: ...
+    @property
+    def urn(self) -> str: ...
+    @property
     def version(self) -> int: ...
     @property
     def variant(self) -> str: ...
-    def short_id(self) -> str: ...
+    def short_id(self, full: bool = False, sortable: bool = False) -> str: ...
+    @staticmethod
+    def from_short_id(s: str, sortable: bool = False) -> UUID: ...
     def base64(self) -> str: ...
+    def to_base64(self) -> str: ...
+    @staticmethod
+    def from_base64(s: str) -> UUID: ...
+    def to_base64url(self) -> str: ...
+    @staticmethod
+    def from_base64url(s: str) -> UUID: ...
     def int(self) -> int: ...
+    def base36(self) -> str: ...
+    @staticmethod
+    def from_base36(s: str) -> UUID: ...
+    def base32(self) -> str: ...
+    @staticmethod
+    def from_base32(s: str) -> UUID: ...
+    def base58(self) -> str: ...
+    @staticmethod
+    def from_base58(s: str) -> UUID: ...
+    def to_ipv6(self) -> IPv6Address: ...
+    @staticmethod
+    def from_ipv6(address: Union[IPv6Address, str]) -> UUID: ...
+    def to_filename(self) -> str: ...
+    @staticmethod
+    def from_filename(s: str) -> UUID: ...
+    def to_url_component(self) -> str: ...
+    @staticmethod
+    def from_url_component(s: str) -> UUID: ...
+    def to_header(self) -> str: ...
+    @staticmethod
+    def from_header(s: str) -> UUID: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def __eq__(self, other: object) -> bool: ...
+    def __lt__(self, other: Union[UUID, str, bytes]) -> bool: ...
+    def __le__(self, other: Union[UUID, str, bytes]) -> bool: ...
+    def __gt__(self, other: Union[UUID, str, bytes]) -> bool: ...
+    def __ge__(self, other: Union[UUID, str, bytes]) -> bool: ...
     def __hash__(self) -> int: ...
+    def __reduce__(self) -> Tuple[type, Tuple[None, bytes]]: ...
+    def __getstate__(self) -> bytes: ...
+    def __copy__(self) -> UUID: ...
+    def __deepcopy__(self, memo: Any) -> UUID: ...
+
+class StreamState(TypedDict, total=False):
+    kind: str
+    last: Optional[str]
+    count: int
+    overflow: Literal["wait", "borrow", "raise"]
+    size: int
+
+class IdStream(Iterator[Union[UUID, ULID, str]]):
+    def __init__(
+        self,
+        kind: Literal["uuid7", "ulid", "uuid4", "nano_id"] = "uuid7",
+        chunk_size: Optional[int] = None,
+        overflow: Literal["wait", "borrow", "raise"] = "borrow",
+        size: Optional[int] = None,
+    ) -> None: ...
+    @property
+    def kind(self) -> str: ...
+    def __iter__(self) -> IdStream: ...
+    def __next__(self) -> Union[UUID, ULID, str]: ...
+    def take(self, n: int) -> List[Union[UUID, ULID, str]]: ...
+    def state(self) -> StreamState: ...
+    @staticmethod
+    def resume(state: StreamState, chunk_size: Optional[int] = None) -> IdStream: ...
+
+class ArrowIdStream:
+    def __arrow_c_stream__(self, requested_schema: Optional[Any] = None) -> Any: ...
+
+class ArrowIdArray:
+    def __arrow_c_array__(self, requested_schema: Optional[Any] = None) -> Tuple[Any, Any]: ...
+    def __len__(self) -> int: ...
+
+class CompactIdGenerator:
+    def __init__(
+        self,
+        device_id: int,
+        time_bits: int = 28,
+        device_bits: int = 12,
+        counter_bits: int = 8,
+        epoch_ms: int = 1_704_067_200_000,
+        unit_ms: int = 1000,
+    ) -> None: ...
+    @property
+    def device_id(self) -> int: ...
+    def next_id(self) -> int: ...
+    def next_bytes(self) -> bytes: ...
+    def next_batch(self, count: int) -> List[int]: ...
+    def next_packed(self, count: int) -> bytes: ...
+    def decode(self, id: int) -> Tuple[int, int, int]: ...
+
+class Snowflake:
+    def __init__(
+        self,
+        worker_id: int = 0,
+        datacenter_id: int = 0,
+        epoch: Union[int, datetime, None] = None,
+        layout: Union[str, Tuple[int, int, int], None] = None,
+        datacenter_bits: int = 5,
+    ) -> None: ...
+    @property
+    def worker_id(self) -> int: ...
+    @property
+    def datacenter_id(self) -> int: ...
+    @property
+    def epoch_ms(self) -> int: ...
+    def next_id(self) -> int: ...
+    def next_batch(self, n: int) -> List[int]: ...
+    def decode(self, id: int) -> Tuple[int, int, int, int]: ...
+
+class UuidArray(Sequence[UUID]):
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> UUID: ...  # type: ignore[override]
+    def __iter__(self) -> Iterator[UUID]: ...
+    def __contains__(self, id: object) -> bool: ...
+    def index(self, id: Union[UUID, str, bytes]) -> int: ...  # type: ignore[override]
+    def __bytes__(self) -> bytes: ...
+    def to_list(self) -> List[UUID]: ...
+    def to_strings(self) -> List[str]: ...
 
-def uuid1() -> UUID: ...
+class IdFileReader(Iterator[UuidArray]):
+    def __next__(self) -> UuidArray: ...
+
+class BinaryIdReader(Iterator[UuidArray]):
+    def __init__(self, source: Any, width: int = 16, chunk_size: Optional[int] = None) -> None: ...
+    def __next__(self) -> UuidArray: ...
+    @property
+    def pending(self) -> int: ...
+
+class IdSet:
+    def __init__(self, ids: Optional[Iterable[Union[int, UUID, str, bytes]]] = None) -> None: ...
+    def add(self, id: Union[int, UUID, str, bytes]) -> None: ...
+    def discard(self, id: Union[int, UUID, str, bytes]) -> None: ...
+    def update(self, ids: Iterable[Union[int, UUID, str, bytes]]) -> None: ...
+    def __contains__(self, id: object) -> bool: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[int]: ...
+    def union(self, other: IdSet) -> IdSet: ...
+    def intersection(self, other: IdSet) -> IdSet: ...
+    def difference(self, other: IdSet) -> IdSet: ...
+    def __or__(self, other: IdSet) -> IdSet: ...
+    def __and__(self, other: IdSet) -> IdSet: ...
+    def __sub__(self, other: IdSet) -> IdSet: ...
+    def serialize(self) -> bytes: ...
+    @staticmethod
+    def deserialize(data: bytes) -> IdSet: ...
+
+class ULID:
+    def __init__(self, value: Union[str, bytes, UUID]) -> None: ...
+    @property
+    def bytes(self) -> bytes: ...
+    @property
+    def int(self) -> int: ...
+    @property
+    def timestamp_ms(self) -> int: ...
+    @property
+    def datetime(self) -> datetime: ...
+    def to_uuid(self) -> UUID: ...
+    @staticmethod
+    def from_uuid(id: Union[UUID, str, bytes]) -> ULID: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __lt__(self, other: ULID) -> bool: ...
+    def __le__(self, other: ULID) -> bool: ...
+    def __gt__(self, other: ULID) -> bool: ...
+    def __ge__(self, other: ULID) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class UlidFactoryState(TypedDict, total=False):
+    last: Optional[str]
+    overflow: Literal["wait", "borrow", "raise"]
+
+class MonotonicUlidFactory:
+    def __init__(self, overflow: Literal["wait", "borrow", "raise"] = "borrow") -> None: ...
+    def __call__(self) -> ULID: ...
+    def batch(self, count: int) -> List[ULID]: ...
+    def state(self) -> UlidFactoryState: ...
+    @staticmethod
+    def resume(state: UlidFactoryState) -> MonotonicUlidFactory: ...
+
+class TypeID:
+    def __init__(self, prefix: str = "", id: Union[UUID, str, bytes, None] = None) -> None: ...
+    @staticmethod
+    def from_string(s: str) -> TypeID: ...
+    @staticmethod
+    def from_uuid(prefix: str, id: Union[UUID, str, bytes]) -> TypeID: ...
+    @staticmethod
+    def is_valid(s: str, prefix: Optional[str] = None) -> bool: ...
+    @property
+    def prefix(self) -> str: ...
+    @property
+    def suffix(self) -> str: ...
+    def to_uuid(self) -> UUID: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class PrefixRegistry:
+    def __init__(self) -> None: ...
+    def register(self, prefix: str, owner: str) -> None: ...
+    def unregister(self, prefix: str) -> None: ...
+    def owner(self, prefix: str) -> Optional[str]: ...
+    def check(self, prefix: str) -> None: ...
+    def prefixes(self) -> Dict[str, str]: ...
+    def typeid(self, prefix: str) -> TypeID: ...
+    def sortable_key(self, prefix: str, reverse: bool = False) -> str: ...
+    def parse(self, s: str) -> TypeID: ...
+    def __contains__(self, prefix: str) -> bool: ...
+    def __len__(self) -> int: ...
+
+def uuid1(privacy: bool = False, node: Optional[int] = None, clock_seq: Optional[int] = None) -> UUID: ...
 def uuid4() -> UUID: ...
-def uuid7() -> UUID: ...
-def uuid4_batch(count: int) -> List[UUID]: ...
-def uuid7_batch(count: int) -> List[UUID]: ...
-def short_id() -> str: ...
-def short_id_batch(count: int) -> List[str]: ...
-def nano_id(size: Optional[int] = None) -> str: ...
-def nano_id_batch(count: int, size: Optional[int] = None) -> List[str]: ...
+def uuid6(privacy: bool = False, node: Optional[int] = None, clock_seq: Optional[int] = None) -> UUID: ...
+def uuid7(reverse: bool = False) -> UUID: ...
+def uuid7_monotonic(overflow: Literal["borrow", "wait", "raise"] = "borrow") -> UUID: ...
+@overload
+def uuid7_monotonic_batch(count: int, overflow: Literal["borrow", "wait", "raise"] = "borrow", returns: Literal["list"] = "list") -> List[UUID]: ...
+@overload
+def uuid7_monotonic_batch(count: int, overflow: Literal["borrow", "wait", "raise"] = "borrow", *, returns: Literal["str"]) -> List[str]: ...
+@overload
+def uuid7_monotonic_batch(count: int, overflow: Literal["borrow", "wait", "raise"] = "borrow", *, returns: Literal["bytes"]) -> bytes: ...
+@overload
+def uuid7_monotonic_batch(count: int, overflow: Literal["borrow", "wait", "raise"] = "borrow", *, returns: Literal["array"]) -> UuidArray: ...
+@overload
+def uuid7_monotonic_batch(count: int, overflow: Literal["borrow", "wait", "raise"] = "borrow", *, returns: Literal["iter"]) -> Iterator[UUID]: ...
+def uuid3(namespace: Union[UUID, str, bytes], name: Union[str, bytes]) -> UUID: ...
+def uuid5(namespace: Union[UUID, str, bytes], name: Union[str, bytes]) -> UUID: ...
+def uuid8(custom: Union[bytes, int]) -> UUID: ...
+@overload
+def uuid4_batch(count: int, returns: Literal["list"] = "list", unique: bool = False) -> List[UUID]: ...
+@overload
+def uuid4_batch(count: int, returns: Literal["str"], unique: bool = False) -> List[str]: ...
+@overload
+def uuid4_batch(count: int, returns: Literal["bytes"], unique: bool = False) -> bytes: ...
+@overload
+def uuid4_batch(count: int, returns: Literal["array"], unique: bool = False) -> UuidArray: ...
+@overload
+def uuid4_batch(count: int, returns: Literal["iter"], unique: bool = False) -> Iterator[UUID]: ...
+@overload
+def uuid7_batch(count: int, reverse: bool = False, returns: Literal["list"] = "list", unique: bool = False) -> List[UUID]: ...
+@overload
+def uuid7_batch(count: int, reverse: bool = False, *, returns: Literal["str"], unique: bool = False) -> List[str]: ...
+@overload
+def uuid7_batch(count: int, reverse: bool = False, *, returns: Literal["bytes"], unique: bool = False) -> bytes: ...
+@overload
+def uuid7_batch(count: int, reverse: bool = False, *, returns: Literal["array"], unique: bool = False) -> UuidArray: ...
+@overload
+def uuid7_batch(count: int, reverse: bool = False, *, returns: Literal["iter"], unique: bool = False) -> Iterator[UUID]: ...
+@overload
+def uuid7_batch_partition(
+    total: int,
+    workers: int,
+    worker_index: int,
+    timestamp: Union[int, datetime, None] = None,
+    start: int = 0,
+    returns: Literal["list"] = "list",
+) -> List[UUID]: ...
+@overload
+def uuid7_batch_partition(
+    total: int,
+    workers: int,
+    worker_index: int,
+    timestamp: Union[int, datetime, None] = None,
+    start: int = 0,
+    *,
+    returns: Literal["str"],
+) -> List[str]: ...
+@overload
+def uuid7_batch_partition(
+    total: int,
+    workers: int,
+    worker_index: int,
+    timestamp: Union[int, datetime, None] = None,
+    start: int = 0,
+    *,
+    returns: Literal["bytes"],
+) -> bytes: ...
+@overload
+def uuid7_batch_partition(
+    total: int,
+    workers: int,
+    worker_index: int,
+    timestamp: Union[int, datetime, None] = None,
+    start: int = 0,
+    *,
+    returns: Literal["array"],
+) -> UuidArray: ...
+@overload
+def uuid7_batch_partition(
+    total: int,
+    workers: int,
+    worker_index: int,
+    timestamp: Union[int, datetime, None] = None,
+    start: int = 0,
+    *,
+    returns: Literal["iter"],
+) -> Iterator[UUID]: ...
+@overload
+def uuid6_batch(
+    count: int,
+    privacy: bool = False,
+    returns: Literal["list"] = "list",
+    node: Optional[int] = None,
+    clock_seq: Optional[int] = None,
+) -> List[UUID]: ...
+@overload
+def uuid6_batch(
+    count: int,
+    privacy: bool = False,
+    *,
+    returns: Literal["str"],
+    node: Optional[int] = None,
+    clock_seq: Optional[int] = None,
+) -> List[str]: ...
+@overload
+def uuid6_batch(
+    count: int,
+    privacy: bool = False,
+    *,
+    returns: Literal["bytes"],
+    node: Optional[int] = None,
+    clock_seq: Optional[int] = None,
+) -> bytes: ...
+@overload
+def uuid6_batch(
+    count: int,
+    privacy: bool = False,
+    *,
+    returns: Literal["array"],
+    node: Optional[int] = None,
+    clock_seq: Optional[int] = None,
+) -> UuidArray: ...
+@overload
+def uuid6_batch(
+    count: int,
+    privacy: bool = False,
+    *,
+    returns: Literal["iter"],
+    node: Optional[int] = None,
+    clock_seq: Optional[int] = None,
+) -> Iterator[UUID]: ...
+@overload
+def uuid3_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["list"] = "list") -> List[UUID]: ...
+@overload
+def uuid3_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["str"]) -> List[str]: ...
+@overload
+def uuid3_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["bytes"]) -> bytes: ...
+@overload
+def uuid3_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["array"]) -> UuidArray: ...
+@overload
+def uuid3_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["iter"]) -> Iterator[UUID]: ...
+@overload
+def uuid5_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["list"] = "list") -> List[UUID]: ...
+@overload
+def uuid5_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["str"]) -> List[str]: ...
+@overload
+def uuid5_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["bytes"]) -> bytes: ...
+@overload
+def uuid5_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["array"]) -> UuidArray: ...
+@overload
+def uuid5_batch(namespace: Union[UUID, str, bytes], names: Sequence[Union[str, bytes]], returns: Literal["iter"]) -> Iterator[UUID]: ...
+def uuid7_with_str_batch(count: int) -> List[Tuple[UUID, str]]: ...
+def uuid_bytes_batch(count: int, kind: str = "uuid7", layout: str = "aos") -> bytes: ...
+def uuid4_bytes_bulk(count: int) -> bytes: ...
+def uuid7_bytes_bulk(count: int) -> bytes: ...
+def uuid4_fill(buffer: Any) -> int: ...
+def uuid7_fill(buffer: Any) -> int: ...
+def write_index(
+    path: Union[str, os.PathLike[str]], ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]]
+) -> int: ...
+def contains_batch(
+    index_path: Union[str, os.PathLike[str]], ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]]
+) -> List[bool]: ...
+def uuid7_seq(key: Union[str, bytes], n: int, start: int = 0, epoch_ms: int = 1_577_836_800_000) -> List[UUID]: ...
+def derive_uuid(seed: Union[str, bytes], info: Union[str, bytes], n: int, start: int = 0) -> List[UUID]: ...
+def idempotency_key(
+    scope: str,
+    *parts: Union[str, bytes, int, bool, None, UUID],
+    key: Union[str, bytes, None] = None,
+) -> UUID: ...
+def short_id(full: bool = False, monotonic: bool = False, sortable: bool = False) -> str: ...
+def short_id_batch(
+    count: int, full: bool = False, monotonic: bool = False, unique: bool = False, sortable: bool = False
+) -> List[str]: ...
+def base58_id() -> str: ...
+def decode_short_id(s: str, sortable: bool = False) -> UUID: ...
+def nano_id(size: Optional[int] = None, alphabet: Optional[str] = None, secure: bool = True) -> str: ...
+def nano_id_batch(count: int, size: Optional[int] = None, alphabet: Optional[str] = None, secure: bool = True, unique: bool = False) -> List[str]: ...
+def cuid2(length: Optional[int] = None) -> str: ...
+def cuid2_batch(count: int, length: Optional[int] = None, unique: bool = False) -> List[str]: ...
+def arrow_stream(
+    kind: str = "uuid7",
+    count: Optional[int] = None,
+    batch_size: Optional[int] = None,
+    column: str = "id",
+    format: str = "string",
+) -> ArrowIdStream: ...
+def uuid4_arrow(count: int, format: Literal["binary", "string"] = "binary") -> ArrowIdArray: ...
+def uuid7_arrow(count: int, format: Literal["binary", "string"] = "binary") -> ArrowIdArray: ...
+@overload
+def generate_columns(columns: Mapping[str, str], count: int, format: Literal["dict"] = "dict") -> Dict[str, List[str]]: ...
+@overload
+def generate_columns(columns: Mapping[str, str], count: int, format: Literal["arrow"]) -> ArrowIdStream: ...
+def write_copy(
+    path_or_fd: Union[str, os.PathLike[str], int, IO[Any]],
+    count: int,
+    columns: Optional[Sequence[Tuple[str, str]]] = None,
+    format: str = "text",
+    header: bool = False,
+) -> int: ...
+def write_parquet(
+    path: Union[str, os.PathLike[str]],
+    count: int,
+    kind: str = "uuid7",
+    column: str = "id",
+    format: str = "string",
+    row_group_size: Optional[int] = None,
+) -> int: ...
+def add_check_digit(s: str, scheme: str = "damm", alphabet: Optional[str] = None) -> str: ...
+def verify_check_digit(s: str, scheme: str = "damm", alphabet: Optional[str] = None) -> bool: ...
+def coupon_code(
+    pattern: str = "XXXX-XXXX",
+    exclude: Sequence[str] = ("0", "O", "I", "1"),
+    check: Optional[Literal["damm", "luhn", "verhoeff"]] = None,
+) -> str: ...
+def coupon_code_batch(
+    count: int,
+    pattern: str = "XXXX-XXXX",
+    exclude: Sequence[str] = ("0", "O", "I", "1"),
+    check: Optional[Literal["damm", "luhn", "verhoeff"]] = None,
+) -> List[str]: ...
+def is_valid_coupon_code(
+    code: str,
+    pattern: str = "XXXX-XXXX",
+    exclude: Sequence[str] = ("0", "O", "I", "1"),
+    check: Optional[Literal["damm", "luhn", "verhoeff"]] = None,
+) -> bool: ...
+def codename(
+    id: Union[UUID, str, bytes],
+    wordlist: Union[str, Tuple[Sequence[str], Sequence[str]], None] = None,
+) -> str: ...
+def to_color(id: Union[UUID, str, bytes]) -> str: ...
+def to_identicon_seed(id: Union[UUID, str, bytes]) -> int: ...
+def distance(a: Union[UUID, str, bytes], b: Union[UUID, str, bytes]) -> int: ...
+def prefix_match_len(a: Union[UUID, str, bytes], b: Union[UUID, str, bytes]) -> int: ...
+def is_monotonic(ids: Union[UuidArray, Iterable[Union[UUID, ULID, str, bytes]]], strict: bool = True) -> bool: ...
+def first_violation(ids: Union[UuidArray, Iterable[Union[UUID, ULID, str, bytes]]], strict: bool = True) -> Optional[int]: ...
+def vectors(kind: Literal["uuid4", "uuid7", "random", "edge"], seed: int = 0, count: int = 16) -> List[Dict[str, str]]: ...
+def recode(s: str, from_alphabet: str, to_alphabet: str) -> str: ...
+def encode(id: Union[UUID, str, bytes], alphabet: str) -> str: ...
+def decode(s: str, alphabet: str) -> UUID: ...
+def upgrade_to_v7(id: Union[UUID, str, bytes]) -> UUID: ...
+def rewrap_random_as_v4(data: Union[UUID, str, bytes]) -> UUID: ...
+@overload
+def normalize_batch(ids: Sequence[str], errors: Literal["raise"] = "raise") -> List[str]: ...
+@overload
+def normalize_batch(ids: Sequence[str], errors: Literal["none"]) -> List[Optional[str]]: ...
+def parse(s: str) -> UUID: ...
+def is_valid(s: str) -> bool: ...
+@overload
+def parse_batch(ids: Union[Iterable[Union[str, bytes]], Any], errors: Literal["raise"] = "raise") -> List[UUID]: ...
+@overload
+def parse_batch(ids: Union[Iterable[Union[str, bytes]], Any], errors: Literal["none"]) -> List[Optional[UUID]]: ...
+@overload
+def parse_batch(
+    ids: Union[Iterable[Union[str, bytes]], Any], errors: Literal["indices"]
+) -> Tuple[List[UUID], List[int]]: ...
+def validate(
+    id: Union[UUID, str, bytes],
+    level: Literal["format", "version", "strict"] = "format",
+    tolerance_ms: int = 60_000,
+) -> bool: ...
+def validate_batch(
+    ids: Union[Iterable[Union[str, bytes]], Any],
+    level: Literal["format", "version", "strict"] = "format",
+    tolerance_ms: int = 60_000,
+) -> List[bool]: ...
+def detect_anomalies(
+    ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]],
+    now: Union[int, datetime, None] = None,
+    launched_at: Union[int, datetime, None] = None,
+    tolerance_ms: int = 60_000,
+    max_skew_ms: int = 60_000,
+) -> List[Tuple[int, Literal["future", "before_launch", "out_of_sequence"]]]: ...
+def route_by_time(id: Union[UUID, ULID, str, bytes], shards: int, window: Union[str, int, timedelta] = "1h") -> int: ...
+def time_window(id: Union[UUID, ULID, str, bytes], window: Union[str, int, timedelta] = "1h") -> Tuple[int, int]: ...
+def expiring_id(ttl: Union[str, int, timedelta]) -> UUID: ...
+def expires_at(id: Union[UUID, str, bytes]) -> datetime: ...
+def is_expired(id: Union[UUID, str, bytes]) -> bool: ...
+def region_id(region: int, bits: int = 8) -> UUID: ...
+def region_of(id: Union[UUID, str, bytes], bits: int = 8) -> int: ...
+def read_ids(
+    path: Union[str, os.PathLike[str]],
+    format: Literal["auto", "text", "binary"] = "auto",
+    chunk_size: Optional[int] = None,
+) -> IdFileReader: ...
+def sortable_key(prefix: Optional[str] = None, reverse: bool = False) -> str: ...
+def parse_sortable_key(key: str, reverse: bool = False) -> Tuple[str, UUID, int]: ...
+def salted_key(id: Union[UUID, str, bytes], buckets: int = 16) -> str: ...
+def unsalt_key(key: str, buckets: Optional[int] = None) -> str: ...
+def new_request_id() -> str: ...
+def current_request_id() -> Optional[str]: ...
+def set_request_id(request_id: str) -> Token[Optional[str]]: ...
+def reset_request_id(token: Token[Optional[str]]) -> None: ...
+def trace_id() -> str: ...
+def span_id() -> str: ...
+def trace_id_batch(count: int) -> List[str]: ...
+def span_id_batch(count: int) -> List[str]: ...
+def should_sample(trace_id: Union[str, bytes, int, UUID], ratio: float) -> bool: ...
+def uuid7_backfill_batch(
+    count: int,
+    start: Union[int, datetime],
+    end: Union[int, datetime],
+    distribution: Literal["uniform", "poisson"] = "uniform",
+) -> List[UUID]: ...
+@overload
+def sample_keys(population: int, count: int, distribution: Literal["zipf", "uniform"] = "zipf", s: float = 1.2) -> List[int]: ...
+@overload
+def sample_keys(population: Iterable[_T], count: int, distribution: Literal["zipf", "uniform"] = "zipf", s: float = 1.2) -> List[_T]: ...
+
+class ClockReport(TypedDict):
+    ok: bool
+    resolution_ns: Optional[int]
+    monotonic: bool
+
+class SelfCheckReport(TypedDict):
+    ok: bool
+    entropy: bool
+    clock: ClockReport
+    generators: Dict[str, bool]
+
+class CollisionReport(TypedDict):
+    ids: int
+    mean: float
+    expected: float
+    collisions: List[int]
+
+def simulate_collisions(kind: str, rate_per_sec: float, duration: float, trials: int = 1) -> CollisionReport: ...
+def pseudonymize_file(
+    in_path: Union[str, os.PathLike[str]],
+    out_path: Union[str, os.PathLike[str]],
+    column: Union[str, int],
+    key: Union[str, bytes],
+    delimiter: Optional[str] = None,
+    header: bool = True,
+) -> int: ...
+def pseudonymize_ids(ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]], key: Union[str, bytes]) -> List[UUID]: ...
+def rekey(
+    ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]],
+    old_key: Union[str, bytes],
+    new_key: Union[str, bytes],
+) -> List[UUID]: ...
+def find_gaps(
+    sorted_ids: Sequence[int],
+    layout: Union[Literal["snowflake", "compact"], Tuple[int, int, int], None] = None,
+) -> List[Tuple[int, int]]: ...
+def intern_strings(enabled: bool = True, capacity: int = 65536) -> None: ...
+def hash_batch(ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]]) -> List[int]: ...
+def set_hash_mode(mode: Literal["stable", "stdlib"]) -> None: ...
+def get_hash_mode() -> Literal["stable", "stdlib"]: ...
+def set_strict_equality(enabled: bool = True) -> None: ...
+def format_batch(
+    ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]],
+    style: Literal["canonical", "hex", "urn", "base64url"] = "canonical",
+) -> List[str]: ...
+def set_uppercase(enabled: bool = True) -> None: ...
+def group(s: str, size: int = 4, sep: str = "-") -> str: ...
+def ungroup(s: str, sep: str = "-") -> str: ...
+def self_check() -> SelfCheckReport: ...
+def set_audit_hook(hook: Optional[Callable[[str, Any, float], Any]], sample_rate: float = 1.0) -> None: ...
+def pack_ids(ids: Union[UuidArray, Iterable[Union[UUID, str, bytes]]]) -> bytes: ...
+@overload
+def unpack_ids(buf: Union[bytes, bytearray, memoryview], returns: Literal["list"] = "list") -> List[UUID]: ...
+@overload
+def unpack_ids(buf: Union[bytes, bytearray, memoryview], returns: Literal["str"]) -> List[str]: ...
+@overload
+def unpack_ids(buf: Union[bytes, bytearray, memoryview], returns: Literal["bytes"]) -> bytes: ...
+@overload
+def unpack_ids(buf: Union[bytes, bytearray, memoryview], returns: Literal["array"]) -> UuidArray: ...
+@overload
+def unpack_ids(buf: Union[bytes, bytearray, memoryview], returns: Literal["iter"]) -> Iterator[UUID]: ...
+def typeid(prefix: str = "") -> TypeID: ...
+def ulid(reverse: bool = False) -> ULID: ...
+def ulid_batch(count: int, reverse: bool = False, unique: bool = False) -> List[ULID]: ...
+def ulid_str_to_uuid_str(s: str) -> str: ...
+def uuid_str_to_ulid_str(s: str) -> str: ...
+def ulid_str_to_uuid_str_batch(values: Sequence[str]) -> List[str]: ...
+def uuid_str_to_ulid_str_batch(values: Sequence[str]) -> List[str]: ...
+
+alphabets: ModuleType
 
 NAMESPACE_DNS: UUID
 NAMESPACE_URL: UUID
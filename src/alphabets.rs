@@ -0,0 +1,42 @@
+//! Common digit alphabets, exposed to Python as `rustid.alphabets`.
+
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+
+/// nano_id's default: digits, upper, lower, `-` and `_`.
+pub(crate) const URL_SAFE: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_";
+pub(crate) const ALPHANUMERIC: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+pub(crate) const BASE36: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+/// Bitcoin base58: alphanumerics without `0`, `O`, `I` and `l`.
+pub(crate) const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// Crockford base32: no `I`, `L`, `O` or `U`.
+pub(crate) const CROCKFORD32: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+pub(crate) const HEX_LOWER: &str = "0123456789abcdef";
+pub(crate) const HEX_UPPER: &str = "0123456789ABCDEF";
+/// Alphanumerics without look-alike characters (`1lI`, `0Oo`, `5S`, `2Z`, `uvV`).
+pub(crate) const NOLOOKALIKES: &str = "346789ABCDEFGHJKLMNPQRTUVWXYabcdefghijkmnpqrtwxyz";
+pub(crate) const NUMBERS: &str = "0123456789";
+/// URL-safe base64 symbols in ASCII order, so encoded text sorts like the bytes.
+pub(crate) const SORTABLE64: &str = "-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+/// `URL_SAFE` as symbols, for the nano ID generators.
+pub(crate) fn url_safe_symbols() -> &'static [char] {
+    static SYMBOLS: OnceLock<Vec<char>> = OnceLock::new();
+    SYMBOLS.get_or_init(|| URL_SAFE.chars().collect())
+}
+
+pub(crate) fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
+    let m = PyModule::new(parent.py(), "alphabets")?;
+    m.add("URL_SAFE", URL_SAFE)?;
+    m.add("ALPHANUMERIC", ALPHANUMERIC)?;
+    m.add("BASE36", BASE36)?;
+    m.add("BASE58", BASE58)?;
+    m.add("CROCKFORD32", CROCKFORD32)?;
+    m.add("HEX_LOWER", HEX_LOWER)?;
+    m.add("HEX_UPPER", HEX_UPPER)?;
+    m.add("NOLOOKALIKES", NOLOOKALIKES)?;
+    m.add("NUMBERS", NUMBERS)?;
+    m.add("SORTABLE64", SORTABLE64)?;
+    parent.add_submodule(&m)
+}
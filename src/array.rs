@@ -0,0 +1,172 @@
+//! Packed arrays of 16-byte IDs.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use rayon::prelude::*;
+
+use crate::{canonical_string, UUID};
+
+/// IDs stored contiguously in Rust, materialized as `UUID` objects only when
+/// indexed or iterated.
+#[pyclass(sequence)]
+pub struct UuidArray {
+    pub(crate) ids: Vec<[u8; 16]>,
+    /// Position of the first occurrence of each ID, built on the first lookup.
+    index: OnceLock<HashMap<[u8; 16], usize>>,
+}
+
+impl UuidArray {
+    pub(crate) fn new(ids: Vec<[u8; 16]>) -> Self {
+        UuidArray { ids, index: OnceLock::new() }
+    }
+
+    fn position(&self, py: Python<'_>, id: &[u8; 16]) -> Option<usize> {
+        let index = match self.index.get() {
+            Some(index) => index,
+            None => {
+                let built = py.allow_threads(|| {
+                    let mut index = HashMap::with_capacity(self.ids.len());
+                    for (i, id) in self.ids.iter().enumerate() {
+                        index.entry(*id).or_insert(i);
+                    }
+                    index
+                });
+                self.index.get_or_init(|| built)
+            }
+        };
+        index.get(id).copied()
+    }
+}
+
+/// Byte values of a `UuidArray` or of any iterable of IDs accepted by
+/// `uuid_bytes`.
+pub(crate) fn batch_bytes(ids: &Bound<'_, PyAny>) -> PyResult<Vec<[u8; 16]>> {
+    if let Ok(array) = ids.downcast::<UuidArray>() {
+        return Ok(array.borrow().ids.clone());
+    }
+    ids.try_iter()?.map(|id| crate::uuid_bytes(&id?)).collect()
+}
+
+#[pymethods]
+impl UuidArray {
+    fn __len__(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<UUID> {
+        let len = self.ids.len() as isize;
+        let pos = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&pos) {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>("UuidArray index out of range"));
+        }
+        Ok(UUID { bytes: self.ids[pos as usize] })
+    }
+
+    /// O(1) membership test after a one-time O(n) index build.
+    fn __contains__(&self, py: Python<'_>, id: &Bound<'_, PyAny>) -> bool {
+        match crate::uuid_bytes(id) {
+            Ok(id) => self.position(py, &id).is_some(),
+            Err(_) => false,
+        }
+    }
+
+    /// Position of the first occurrence of `id`; raises `ValueError` if absent.
+    fn index(&self, py: Python<'_>, id: &Bound<'_, PyAny>) -> PyResult<usize> {
+        let bytes = crate::uuid_bytes(id)?;
+        self.position(py, &bytes)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("ID is not in the array"))
+    }
+
+    fn __iter__(slf: Bound<'_, Self>) -> UuidArrayIter {
+        UuidArrayIter { array: slf.unbind(), pos: 0 }
+    }
+
+    /// All IDs packed back to back, 16 bytes each.
+    fn __bytes__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.ids.as_flattened())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("UuidArray(len={})", self.ids.len())
+    }
+
+    fn to_list(&self) -> Vec<UUID> {
+        self.ids.iter().map(|&bytes| UUID { bytes }).collect()
+    }
+
+    /// Canonical strings of all IDs, formatted in parallel.
+    fn to_strings(&self, py: Python<'_>) -> Vec<String> {
+        py.allow_threads(|| self.ids.par_iter().map(canonical_string).collect())
+    }
+}
+
+#[pyclass]
+pub struct UuidArrayIter {
+    array: Py<UuidArray>,
+    pos: usize,
+}
+
+#[pymethods]
+impl UuidArrayIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> Option<UUID> {
+        let bytes = *self.array.borrow(py).ids.get(self.pos)?;
+        self.pos += 1;
+        Some(UUID { bytes })
+    }
+}
+
+/// Representation requested with a batch function's `returns=` argument.
+#[derive(Clone, Copy)]
+pub(crate) enum Returns {
+    /// `list[UUID]`
+    List,
+    /// `list[str]` of canonical strings
+    Str,
+    /// One `bytes` object, 16 bytes per ID
+    Bytes,
+    Array,
+    /// Iterator of `UUID` over a packed array
+    Iter,
+}
+
+impl Returns {
+    pub(crate) fn parse(returns: &str) -> PyResult<Self> {
+        match returns {
+            "list" => Ok(Returns::List),
+            "str" => Ok(Returns::Str),
+            "bytes" => Ok(Returns::Bytes),
+            "array" => Ok(Returns::Array),
+            "iter" => Ok(Returns::Iter),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported returns: {}", returns))),
+        }
+    }
+
+    pub(crate) fn build(self, py: Python<'_>, ids: Vec<[u8; 16]>) -> PyResult<PyObject> {
+        Ok(match self {
+            Returns::List => ids
+                .into_iter()
+                .map(|bytes| UUID { bytes })
+                .collect::<Vec<_>>()
+                .into_pyobject(py)?
+                .into_any()
+                .unbind(),
+            Returns::Str => {
+                let strings: Vec<String> = py.allow_threads(|| ids.par_iter().map(canonical_string).collect());
+                strings.into_pyobject(py)?.into_any().unbind()
+            }
+            Returns::Bytes => PyBytes::new(py, ids.as_flattened()).into_any().unbind(),
+            Returns::Array => Bound::new(py, UuidArray::new(ids))?.into_any().unbind(),
+            Returns::Iter => {
+                let array = Bound::new(py, UuidArray::new(ids))?;
+                Bound::new(py, UuidArray::__iter__(array))?.into_any().unbind()
+            }
+        })
+    }
+}
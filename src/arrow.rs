@@ -0,0 +1,453 @@
+//! Minimal Arrow C data / C stream interface producers.
+//!
+//! Only the handful of layouts rustid emits are implemented: fixed-size
+//! binary(16) and large_utf8 columns, alone or wrapped in a single-column
+//! struct batch. Strings use large_utf8 (64-bit offsets) because utf8's
+//! 32-bit offsets overflow past 2 GiB, about 60M canonical UUIDs.
+
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::ptr;
+
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use rayon::prelude::*;
+
+use crate::audit;
+use crate::stream::IdSource;
+use crate::UUID;
+
+const DEFAULT_BATCH: usize = 65536;
+
+#[repr(C)]
+pub(crate) struct FFI_ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut FFI_ArrowSchema,
+    dictionary: *mut FFI_ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut FFI_ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+pub(crate) struct FFI_ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut FFI_ArrowArray,
+    dictionary: *mut FFI_ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut FFI_ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+pub(crate) struct FFI_ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut FFI_ArrowArrayStream, *mut FFI_ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut FFI_ArrowArrayStream, *mut FFI_ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut FFI_ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut FFI_ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+// The producer structs only travel between threads inside capsules owned by
+// the consumer, which is how the C interface is specified to be used.
+unsafe impl Send for FFI_ArrowSchema {}
+unsafe impl Send for FFI_ArrowArray {}
+unsafe impl Send for FFI_ArrowArrayStream {}
+
+/// Physical layout of an ID column.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ColumnFormat {
+    /// `FixedSizeBinary(16)`
+    Binary,
+    /// large_utf8, canonical hyphenated form
+    String,
+}
+
+impl ColumnFormat {
+    pub(crate) fn parse(format: &str) -> PyResult<Self> {
+        match format {
+            "binary" => Ok(ColumnFormat::Binary),
+            "string" => Ok(ColumnFormat::String),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported arrow format: {}", format))),
+        }
+    }
+
+    fn arrow_format(self) -> &'static CStr {
+        match self {
+            ColumnFormat::Binary => c"w:16",
+            ColumnFormat::String => c"U",
+        }
+    }
+}
+
+struct SchemaPrivate {
+    name: CString,
+    children: Vec<*mut FFI_ArrowSchema>,
+}
+
+unsafe extern "C" fn release_schema(schema: *mut FFI_ArrowSchema) {
+    let schema = &mut *schema;
+    let private = Box::from_raw(schema.private_data as *mut SchemaPrivate);
+    for &child in &private.children {
+        if let Some(release) = (*child).release {
+            release(child);
+        }
+        drop(Box::from_raw(child));
+    }
+    schema.release = None;
+}
+
+fn new_schema(format: &'static CStr, name: &str, children: Vec<FFI_ArrowSchema>) -> FFI_ArrowSchema {
+    let children: Vec<*mut FFI_ArrowSchema> = children.into_iter().map(|c| Box::into_raw(Box::new(c))).collect();
+    let mut private = Box::new(SchemaPrivate {
+        name: CString::new(name).unwrap_or_default(),
+        children,
+    });
+    FFI_ArrowSchema {
+        format: format.as_ptr(),
+        name: private.name.as_ptr(),
+        metadata: ptr::null(),
+        // ARROW_FLAG_NULLABLE is left unset: generated columns never contain nulls.
+        flags: 0,
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+/// Schema of a bare ID column.
+fn column_schema(format: ColumnFormat) -> FFI_ArrowSchema {
+    new_schema(format.arrow_format(), "", Vec::new())
+}
+
+/// Schema of a single-column record batch.
+pub(crate) fn batch_schema(column: &str, format: ColumnFormat) -> FFI_ArrowSchema {
+    new_schema(c"+s", "", vec![new_schema(format.arrow_format(), column, Vec::new())])
+}
+
+struct ArrayPrivate {
+    // Owns the memory behind `buffer_ptrs`.
+    _buffers: Vec<Vec<u8>>,
+    buffer_ptrs: Vec<*const c_void>,
+    children: Vec<*mut FFI_ArrowArray>,
+}
+
+unsafe extern "C" fn release_array(array: *mut FFI_ArrowArray) {
+    let array = &mut *array;
+    let private = Box::from_raw(array.private_data as *mut ArrayPrivate);
+    for &child in &private.children {
+        if let Some(release) = (*child).release {
+            release(child);
+        }
+        drop(Box::from_raw(child));
+    }
+    array.release = None;
+}
+
+fn new_array(length: usize, buffers: Vec<Option<Vec<u8>>>, children: Vec<FFI_ArrowArray>) -> FFI_ArrowArray {
+    let n_buffers = buffers.len();
+    let mut owned = Vec::with_capacity(n_buffers);
+    let mut buffer_ptrs = Vec::with_capacity(n_buffers);
+    for buffer in buffers {
+        match buffer {
+            Some(data) => {
+                buffer_ptrs.push(data.as_ptr() as *const c_void);
+                owned.push(data);
+            }
+            None => buffer_ptrs.push(ptr::null()),
+        }
+    }
+    let children: Vec<*mut FFI_ArrowArray> = children.into_iter().map(|c| Box::into_raw(Box::new(c))).collect();
+    let mut private = Box::new(ArrayPrivate {
+        _buffers: owned,
+        buffer_ptrs,
+        children,
+    });
+    FFI_ArrowArray {
+        length: length as i64,
+        null_count: 0,
+        offset: 0,
+        n_buffers: n_buffers as i64,
+        n_children: private.children.len() as i64,
+        buffers: private.buffer_ptrs.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+/// Column array holding `ids` in the requested layout.
+pub(crate) fn column_array(ids: &[[u8; 16]], format: ColumnFormat) -> FFI_ArrowArray {
+    match format {
+        ColumnFormat::Binary => {
+            let data: Vec<u8> = ids.iter().flatten().copied().collect();
+            new_array(ids.len(), vec![None, Some(data)], Vec::new())
+        }
+        ColumnFormat::String => large_utf8_array(ids.len(), ids.iter().map(crate::encode_canonical)),
+    }
+}
+
+/// Single-column record batch holding `ids`.
+pub(crate) fn batch_array(ids: &[[u8; 16]], format: ColumnFormat) -> FFI_ArrowArray {
+    new_array(ids.len(), vec![None], vec![column_array(ids, format)])
+}
+
+/// Producer of the record batches behind an exported stream.
+pub(crate) trait BatchSource: Send + Sync {
+    fn schema(&self) -> FFI_ArrowSchema;
+    /// Next batch, or `None` once the stream is exhausted.
+    fn next_batch(&mut self) -> PyResult<Option<FFI_ArrowArray>>;
+}
+
+struct GeneratedIds {
+    kind: String,
+    source: IdSource,
+    column: String,
+    format: ColumnFormat,
+    batch_size: usize,
+    remaining: Option<u64>,
+}
+
+impl BatchSource for GeneratedIds {
+    fn schema(&self) -> FFI_ArrowSchema {
+        batch_schema(&self.column, self.format)
+    }
+
+    fn next_batch(&mut self) -> PyResult<Option<FFI_ArrowArray>> {
+        let n = match self.remaining {
+            Some(remaining) => remaining.min(self.batch_size as u64) as usize,
+            None => self.batch_size,
+        };
+        if n == 0 {
+            return Ok(None);
+        }
+        let source = &mut self.source;
+        let ids: Vec<[u8; 16]> = (0..n).map(|_| source.next()).collect();
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= n as u64;
+        }
+        audit::record_detached(&self.kind, ids.iter().map(|&bytes| UUID { bytes }))?;
+        Ok(Some(batch_array(&ids, self.format)))
+    }
+}
+
+/// large_utf8 column array over `len` already formatted values.
+fn large_utf8_array(len: usize, values: impl Iterator<Item = impl AsRef<[u8]>>) -> FFI_ArrowArray {
+    let mut offsets = Vec::with_capacity((len + 1) * 8);
+    let mut data = Vec::with_capacity(len * 36);
+    offsets.extend_from_slice(&0i64.to_ne_bytes());
+    for value in values {
+        data.extend_from_slice(value.as_ref());
+        offsets.extend_from_slice(&(data.len() as i64).to_ne_bytes());
+    }
+    new_array(len, vec![None, Some(offsets), Some(data)], Vec::new())
+}
+
+/// A single record batch of large_utf8 columns.
+pub(crate) struct Utf8Table {
+    names: Vec<String>,
+    columns: Option<Vec<Vec<String>>>,
+}
+
+impl Utf8Table {
+    pub(crate) fn new(names: Vec<String>, columns: Vec<Vec<String>>) -> Self {
+        Utf8Table { names, columns: Some(columns) }
+    }
+}
+
+impl BatchSource for Utf8Table {
+    fn schema(&self) -> FFI_ArrowSchema {
+        let fields = self.names.iter().map(|name| new_schema(c"U", name, Vec::new())).collect();
+        new_schema(c"+s", "", fields)
+    }
+
+    fn next_batch(&mut self) -> PyResult<Option<FFI_ArrowArray>> {
+        let Some(columns) = self.columns.take() else {
+            return Ok(None);
+        };
+        let length = columns.first().map_or(0, Vec::len);
+        let children = columns.iter().map(|values| large_utf8_array(values.len(), values.iter())).collect();
+        Ok(Some(new_array(length, vec![None], children)))
+    }
+}
+
+struct StreamPrivate {
+    source: Box<dyn BatchSource>,
+    last_error: Option<CString>,
+}
+
+/// errno value reported when a batch fails, e.g. because the audit hook raised.
+const EIO: c_int = 5;
+
+unsafe extern "C" fn stream_get_schema(stream: *mut FFI_ArrowArrayStream, out: *mut FFI_ArrowSchema) -> c_int {
+    let private = &*((*stream).private_data as *const StreamPrivate);
+    ptr::write(out, private.source.schema());
+    0
+}
+
+unsafe extern "C" fn stream_get_next(stream: *mut FFI_ArrowArrayStream, out: *mut FFI_ArrowArray) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivate);
+    let batch = match private.source.next_batch() {
+        Ok(batch) => batch,
+        Err(e) => {
+            let message = e.to_string().replace('\0', " ");
+            private.last_error = CString::new(message).ok();
+            return EIO;
+        }
+    };
+    let batch = batch.unwrap_or_else(|| {
+        // A released array marks the end of the stream.
+        let mut end = new_array(0, Vec::new(), Vec::new());
+        release_array(&mut end);
+        end
+    });
+    ptr::write(out, batch);
+    0
+}
+
+unsafe extern "C" fn stream_get_last_error(stream: *mut FFI_ArrowArrayStream) -> *const c_char {
+    let private = &*((*stream).private_data as *const StreamPrivate);
+    private.last_error.as_ref().map_or(ptr::null(), |message| message.as_ptr())
+}
+
+unsafe extern "C" fn stream_release(stream: *mut FFI_ArrowArrayStream) {
+    let stream = &mut *stream;
+    drop(Box::from_raw(stream.private_data as *mut StreamPrivate));
+    stream.release = None;
+}
+
+/// Arrow-exportable stream of generated IDs, consumable once through the
+/// `__arrow_c_stream__` protocol (pyarrow, DuckDB, polars).
+#[pyclass]
+pub struct ArrowIdStream {
+    pending: Option<Box<dyn BatchSource>>,
+}
+
+impl ArrowIdStream {
+    pub(crate) fn new(source: Box<dyn BatchSource>) -> Self {
+        ArrowIdStream { pending: Some(source) }
+    }
+}
+
+#[pymethods]
+impl ArrowIdStream {
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &mut self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let source = self.pending.take().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Arrow stream has already been consumed")
+        })?;
+        let private = StreamPrivate { source, last_error: None };
+        let stream = FFI_ArrowArrayStream {
+            get_schema: Some(stream_get_schema),
+            get_next: Some(stream_get_next),
+            get_last_error: Some(stream_get_last_error),
+            release: Some(stream_release),
+            private_data: Box::into_raw(Box::new(private)) as *mut c_void,
+        };
+        PyCapsule::new_with_destructor(py, stream, Some(c"arrow_array_stream".to_owned()), |mut stream, _| {
+            if let Some(release) = stream.release {
+                unsafe { release(&mut stream) };
+            }
+        })
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (kind="uuid7", count=None, batch_size=None, column="id", format="string"))]
+pub fn arrow_stream(
+    kind: &str,
+    count: Option<u64>,
+    batch_size: Option<usize>,
+    column: &str,
+    format: &str,
+) -> PyResult<ArrowIdStream> {
+    Ok(ArrowIdStream::new(Box::new(GeneratedIds {
+        kind: kind.to_string(),
+        source: IdSource::new(kind)?,
+        column: column.to_string(),
+        format: ColumnFormat::parse(format)?,
+        batch_size: batch_size.unwrap_or(DEFAULT_BATCH).max(1),
+        remaining: count,
+    })))
+}
+
+/// Arrow array of generated IDs, exported through the `__arrow_c_array__`
+/// protocol, e.g. with `pyarrow.array(...)`.
+#[pyclass(frozen)]
+pub struct ArrowIdArray {
+    ids: Vec<[u8; 16]>,
+    format: ColumnFormat,
+}
+
+#[pymethods]
+impl ArrowIdArray {
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<PyObject>,
+    ) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        let _ = requested_schema;
+        let array = py.allow_threads(|| column_array(&self.ids, self.format));
+        let schema = column_schema(self.format);
+        let schema = PyCapsule::new_with_destructor(py, schema, Some(c"arrow_schema".to_owned()), |mut schema, _| {
+            if let Some(release) = schema.release {
+                unsafe { release(&mut schema) };
+            }
+        })?;
+        let array = PyCapsule::new_with_destructor(py, array, Some(c"arrow_array".to_owned()), |mut array, _| {
+            if let Some(release) = array.release {
+                unsafe { release(&mut array) };
+            }
+        })?;
+        Ok((schema, array))
+    }
+
+    fn __len__(&self) -> usize {
+        self.ids.len()
+    }
+}
+
+fn generated_array(
+    py: Python<'_>,
+    count: usize,
+    kind: &str,
+    format: &str,
+    generate: impl Fn() -> [u8; 16] + Sync,
+) -> PyResult<ArrowIdArray> {
+    let format = ColumnFormat::parse(format)?;
+    let ids: Vec<[u8; 16]> = py.allow_threads(|| (0..count).into_par_iter().map(|_| generate()).collect());
+    audit::record(py, kind, ids.iter().map(|&bytes| UUID { bytes }))?;
+    Ok(ArrowIdArray { ids, format })
+}
+
+/// `count` v4 UUIDs as an Arrow array built in Rust: `FixedSizeBinary(16)`
+/// with `format="binary"`, or canonical strings with `format="string"`.
+/// Pass the result to `pyarrow.array()` or any other Arrow PyCapsule
+/// consumer, which skips creating a Python object per ID.
+#[pyfunction]
+#[pyo3(signature = (count, format="binary"))]
+pub fn uuid4_arrow(py: Python<'_>, count: usize, format: &str) -> PyResult<ArrowIdArray> {
+    generated_array(py, count, "uuid4", format, || *uuid::Uuid::new_v4().as_bytes())
+}
+
+/// `count` v7 UUIDs as an Arrow array; see `uuid4_arrow`.
+#[pyfunction]
+#[pyo3(signature = (count, format="binary"))]
+pub fn uuid7_arrow(py: Python<'_>, count: usize, format: &str) -> PyResult<ArrowIdArray> {
+    generated_array(py, count, "uuid7", format, || *uuid::Uuid::now_v7().as_bytes())
+}
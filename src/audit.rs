@@ -0,0 +1,83 @@
+//! Optional issuance audit hook for generated IDs.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use pyo3::prelude::*;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: Mutex<Option<Hook>> = Mutex::new(None);
+
+struct Hook {
+    callback: Py<PyAny>,
+    sample_rate: f64,
+}
+
+/// Report freshly generated `ids` of `kind` to the audit hook, if one is set.
+/// Errors raised by the hook propagate to the generating call.
+pub(crate) fn record<'py, T: IntoPyObject<'py>>(
+    py: Python<'py>,
+    kind: &str,
+    ids: impl IntoIterator<Item = T>,
+) -> PyResult<()> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let (callback, sample_rate) = match HOOK.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        Some(hook) => (hook.callback.clone_ref(py), hook.sample_rate),
+        None => return Ok(()),
+    };
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0);
+    for id in ids {
+        if sample_rate >= 1.0 || fastrand::f64() < sample_rate {
+            callback.call1(py, (kind, id, timestamp))?;
+        }
+    }
+    Ok(())
+}
+
+/// [`record`] from code running without the GIL, which is only taken when a
+/// hook is set.
+pub(crate) fn record_detached<I>(kind: &str, ids: I) -> PyResult<()>
+where
+    I: IntoIterator,
+    I::Item: for<'py> IntoPyObject<'py>,
+{
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    Python::with_gil(|py| record(py, kind, ids))
+}
+
+/// Call `hook(kind, id, timestamp)` for every ID the generators issue, or
+/// for a random `sample_rate` fraction of them; `hook=None` removes it.
+///
+/// `kind` names the generator (`"uuid4"`, `"ulid"`, `"snowflake"`, ...),
+/// `id` is the generated object, string or int, and `timestamp` is Unix
+/// seconds at issuance. Covered are `uuid1` to `uuid8`, their batch, bytes,
+/// fill and Arrow variants, `uuid_bytes_batch`, `uuid7_backfill_batch`,
+/// `ulid`, `typeid`, `short_id`, `nano_id`, `base58_id`, `cuid2`,
+/// `coupon_code`, `expiring_id`, `region_id`, `sortable_key`,
+/// `new_request_id`, `trace_id`, `span_id` and their batches,
+/// `generate_columns`, `write_copy`, `write_parquet`, `arrow_stream`,
+/// `Snowflake`, `CompactIdGenerator`, `MonotonicUlidFactory` and `IdStream`.
+/// IDs derived from existing data (`derive_uuid`, `pseudonymize_ids`,
+/// conversions) and the fixed `vectors()` are not reported. Batch calls
+/// report once the batch is complete, and streaming writers and
+/// `arrow_stream` once per chunk. An exception raised by the hook propagates
+/// to the generating call; in an Arrow stream it fails the batch read.
+#[pyfunction]
+#[pyo3(signature = (hook, sample_rate=1.0))]
+pub fn set_audit_hook(hook: Option<Py<PyAny>>, sample_rate: f64) -> PyResult<()> {
+    if !(sample_rate > 0.0 && sample_rate <= 1.0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("sample_rate must be in (0, 1]"));
+    }
+    let previous = {
+        let mut current = HOOK.lock().unwrap_or_else(|e| e.into_inner());
+        ENABLED.store(hook.is_some(), Ordering::Relaxed);
+        std::mem::replace(&mut *current, hook.map(|callback| Hook { callback, sample_rate }))
+    };
+    drop(previous);
+    Ok(())
+}
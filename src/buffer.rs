@@ -0,0 +1,70 @@
+//! Raw access to objects implementing the buffer protocol.
+
+use std::ffi::CStr;
+
+use pyo3::ffi;
+use pyo3::prelude::*;
+
+/// A C-contiguous buffer view, released when dropped.
+pub(crate) struct RawBuffer {
+    view: Box<ffi::Py_buffer>,
+}
+
+// The exporter keeps the memory alive and pinned until the view is released,
+// so the bytes may be read (or written, for writable views) off the GIL.
+unsafe impl Send for RawBuffer {}
+unsafe impl Sync for RawBuffer {}
+
+impl RawBuffer {
+    pub(crate) fn get(obj: &Bound<'_, PyAny>, writable: bool) -> PyResult<Self> {
+        let mut flags = ffi::PyBUF_C_CONTIGUOUS | ffi::PyBUF_FORMAT;
+        if writable {
+            flags |= ffi::PyBUF_WRITABLE;
+        }
+        let mut view: Box<ffi::Py_buffer> = Box::new(unsafe { std::mem::zeroed() });
+        if unsafe { ffi::PyObject_GetBuffer(obj.as_ptr(), &mut *view, flags) } == -1 {
+            return Err(PyErr::fetch(obj.py()));
+        }
+        Ok(RawBuffer { view })
+    }
+
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        if self.view.len == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.view.buf as *const u8, self.view.len as usize) }
+    }
+
+    /// The bytes of a view obtained with `writable=true`.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        debug_assert!(self.view.readonly == 0, "view was not requested writable");
+        if self.view.len == 0 {
+            return &mut [];
+        }
+        unsafe { std::slice::from_raw_parts_mut(self.view.buf as *mut u8, self.view.len as usize) }
+    }
+
+    pub(crate) fn itemsize(&self) -> usize {
+        self.view.itemsize as usize
+    }
+
+    /// struct-module format string of the items, `"B"` when unspecified.
+    pub(crate) fn format(&self) -> &str {
+        if self.view.format.is_null() {
+            return "B";
+        }
+        unsafe { CStr::from_ptr(self.view.format) }.to_str().unwrap_or("")
+    }
+
+    /// Whether the items are fixed-width byte strings, such as numpy `S36`.
+    pub(crate) fn is_fixed_bytes(&self) -> bool {
+        let format = self.format().trim_start_matches(['@', '=', '<', '>', '!']);
+        format.strip_suffix('s').is_some_and(|n| n.bytes().all(|c| c.is_ascii_digit()))
+    }
+}
+
+impl Drop for RawBuffer {
+    fn drop(&mut self) {
+        Python::with_gil(|_| unsafe { ffi::PyBuffer_Release(&mut *self.view) });
+    }
+}
@@ -0,0 +1,263 @@
+//! Check characters for human-entered codes.
+//!
+//! `damm` uses the classic order-10 table for decimal strings and the
+//! quasigroup `x * y = 2x + y` over GF(2^k) for alphabets of 4, 8, ..., 256
+//! symbols (hex, Crockford base32, URL-safe base64). `verhoeff` is decimal
+//! only; `luhn` is Luhn mod N and accepts any alphabet.
+
+use pyo3::prelude::*;
+
+use crate::encoding::Alphabet;
+
+const DECIMAL: &str = "0123456789";
+
+const DAMM_TABLE: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+const VERHOEFF_D: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+const VERHOEFF_P: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 8, 1, 2, 7, 6, 3, 0],
+    [4, 2, 8, 9, 5, 7, 0, 3, 6, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 2, 6, 3, 1, 5, 8, 9],
+];
+
+const VERHOEFF_INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+// Primitive polynomials for GF(2^k), indexed by k.
+const GF_POLY: [u32; 9] = [0, 0, 0b111, 0b1011, 0b10011, 0b100101, 0b1000011, 0b10000011, 0b100011101];
+
+#[derive(Clone, Copy)]
+enum Scheme {
+    Damm,
+    Verhoeff,
+    Luhn,
+}
+
+fn value_error(msg: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(msg)
+}
+
+pub(crate) struct Checker {
+    scheme: Scheme,
+    alphabet: Vec<char>,
+}
+
+impl Checker {
+    pub(crate) fn new(scheme: &str, alphabet: Option<&str>) -> PyResult<Self> {
+        let alphabet = Alphabet::new(alphabet.unwrap_or(DECIMAL))?.symbols().to_vec();
+        let n = alphabet.len();
+        let scheme = match scheme {
+            "damm" if n == 10 || (n.is_power_of_two() && (4..=256).contains(&n)) => Scheme::Damm,
+            "damm" => return Err(value_error(format!("damm needs a decimal or power-of-two alphabet (4..256), got {} symbols", n))),
+            "verhoeff" if n == 10 => Scheme::Verhoeff,
+            "verhoeff" => return Err(value_error("verhoeff needs a 10-symbol alphabet".to_string())),
+            "luhn" if n >= 2 => Scheme::Luhn,
+            "luhn" => return Err(value_error("luhn needs at least 2 symbols".to_string())),
+            _ => return Err(value_error(format!("Unknown check digit scheme: {}", scheme))),
+        };
+        Ok(Checker { scheme, alphabet })
+    }
+
+    fn digits(&self, s: &str) -> PyResult<Vec<u32>> {
+        s.chars()
+            .map(|c| {
+                self.alphabet
+                    .iter()
+                    .position(|&a| a == c)
+                    .map(|i| i as u32)
+                    .ok_or_else(|| value_error(format!("Character {:?} is not in the check digit alphabet", c)))
+            })
+            .collect()
+    }
+
+    fn gf_double(&self, x: u32) -> u32 {
+        let n = self.alphabet.len() as u32;
+        let doubled = x << 1;
+        if doubled & n != 0 {
+            doubled ^ GF_POLY[n.trailing_zeros() as usize]
+        } else {
+            doubled
+        }
+    }
+
+    fn damm_fold(&self, digits: &[u32]) -> u32 {
+        if self.alphabet.len() == 10 {
+            digits.iter().fold(0, |acc, &d| DAMM_TABLE[acc as usize][d as usize] as u32)
+        } else {
+            digits.iter().fold(0, |acc, &d| self.gf_double(acc) ^ d)
+        }
+    }
+
+    fn verhoeff_fold(digits: &[u32], with_check: bool) -> u8 {
+        let offset = if with_check { 0 } else { 1 };
+        digits
+            .iter()
+            .rev()
+            .enumerate()
+            .fold(0u8, |c, (i, &d)| VERHOEFF_D[c as usize][VERHOEFF_P[(i + offset) % 8][d as usize] as usize])
+    }
+
+    fn luhn_sum(&self, digits: &[u32], with_check: bool) -> u32 {
+        let n = self.alphabet.len() as u32;
+        let mut factor = if with_check { 1 } else { 2 };
+        let mut sum = 0;
+        for &d in digits.iter().rev() {
+            let addend = d * factor;
+            sum += addend / n + addend % n;
+            factor = 3 - factor;
+        }
+        sum % n
+    }
+
+    pub(crate) fn check_digit(&self, digits: &[u32]) -> u32 {
+        match self.scheme {
+            Scheme::Damm if self.alphabet.len() == 10 => {
+                let interim = self.damm_fold(digits);
+                (0..10).find(|&d| DAMM_TABLE[interim as usize][d] == 0).unwrap_or(0) as u32
+            }
+            Scheme::Damm => self.gf_double(self.damm_fold(digits)),
+            Scheme::Verhoeff => VERHOEFF_INV[Self::verhoeff_fold(digits, false) as usize] as u32,
+            Scheme::Luhn => {
+                let n = self.alphabet.len() as u32;
+                (n - self.luhn_sum(digits, false)) % n
+            }
+        }
+    }
+
+    pub(crate) fn is_valid(&self, digits: &[u32]) -> bool {
+        match self.scheme {
+            Scheme::Damm => self.damm_fold(digits) == 0,
+            Scheme::Verhoeff => Self::verhoeff_fold(digits, true) == 0,
+            Scheme::Luhn => self.luhn_sum(digits, true) == 0,
+        }
+    }
+}
+
+/// Append a check character to `s`.
+#[pyfunction]
+#[pyo3(signature = (s, scheme="damm", alphabet=None))]
+pub fn add_check_digit(s: &str, scheme: &str, alphabet: Option<&str>) -> PyResult<String> {
+    let checker = Checker::new(scheme, alphabet)?;
+    let check = checker.check_digit(&checker.digits(s)?);
+    let mut out = String::with_capacity(s.len() + 4);
+    out.push_str(s);
+    out.push(checker.alphabet[check as usize]);
+    Ok(out)
+}
+
+/// Whether the last character of `s` is a valid check character for the rest.
+#[pyfunction]
+#[pyo3(signature = (s, scheme="damm", alphabet=None))]
+pub fn verify_check_digit(s: &str, scheme: &str, alphabet: Option<&str>) -> PyResult<bool> {
+    let checker = Checker::new(scheme, alphabet)?;
+    if s.is_empty() {
+        return Ok(false);
+    }
+    match checker.digits(s) {
+        Ok(digits) => Ok(checker.is_valid(&digits)),
+        Err(_) => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALPHABETS: [&str; 4] =
+        [DECIMAL, crate::alphabets::HEX_LOWER, crate::alphabets::CROCKFORD32, crate::alphabets::URL_SAFE];
+
+    fn checkers() -> Vec<Checker> {
+        let mut checkers = vec![Checker::new("verhoeff", None).unwrap()];
+        for alphabet in ALPHABETS {
+            checkers.push(Checker::new("damm", Some(alphabet)).unwrap());
+            checkers.push(Checker::new("luhn", Some(alphabet)).unwrap());
+        }
+        checkers
+    }
+
+    fn with_check(checker: &Checker, digits: &[u32]) -> Vec<u32> {
+        let mut out = digits.to_vec();
+        out.push(checker.check_digit(digits));
+        out
+    }
+
+    #[test]
+    fn known_values() {
+        let decimal = |s: &str| s.bytes().map(|b| (b - b'0') as u32).collect::<Vec<_>>();
+        assert_eq!(Checker::new("damm", None).unwrap().check_digit(&decimal("572")), 4);
+        assert_eq!(Checker::new("verhoeff", None).unwrap().check_digit(&decimal("236")), 3);
+        assert_eq!(Checker::new("luhn", None).unwrap().check_digit(&decimal("7992739871")), 3);
+    }
+
+    #[test]
+    fn check_digit_round_trips() {
+        for checker in checkers() {
+            let n = checker.alphabet.len() as u32;
+            for len in 0..20 {
+                let digits: Vec<u32> = (0..len).map(|_| fastrand::u32(..n)).collect();
+                assert!(checker.is_valid(&with_check(&checker, &digits)));
+            }
+        }
+    }
+
+    #[test]
+    fn detects_single_substitutions() {
+        for checker in checkers() {
+            let n = checker.alphabet.len() as u32;
+            let digits: Vec<u32> = (0..12).map(|_| fastrand::u32(..n)).collect();
+            let valid = with_check(&checker, &digits);
+            for i in 0..valid.len() {
+                for d in (0..n).filter(|&d| d != valid[i]) {
+                    let mut changed = valid.clone();
+                    changed[i] = d;
+                    assert!(!checker.is_valid(&changed));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn damm_and_verhoeff_detect_adjacent_transpositions() {
+        let mut checkers = vec![Checker::new("verhoeff", None).unwrap()];
+        checkers.extend(ALPHABETS.iter().map(|&alphabet| Checker::new("damm", Some(alphabet)).unwrap()));
+        for checker in checkers {
+            let n = checker.alphabet.len() as u32;
+            let digits: Vec<u32> = (0..12).map(|_| fastrand::u32(..n)).collect();
+            let valid = with_check(&checker, &digits);
+            for i in 0..valid.len() - 1 {
+                if valid[i] != valid[i + 1] {
+                    let mut swapped = valid.clone();
+                    swapped.swap(i, i + 1);
+                    assert!(!checker.is_valid(&swapped));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Compact wire encoding for sets of IDs.
+//!
+//! Layout: magic `RIDP`, format version 1, a LEB128 count, then per ID the
+//! zigzag LEB128 delta of its high 64 bits from the previous ID's, followed
+//! by its low 64 bits verbatim (big-endian). Sorted time-ordered IDs (v7,
+//! ULID) have small high-half deltas, so each takes 11-12 bytes instead
+//! of 16; the random low half cannot be compressed.
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::array::{batch_bytes, Returns};
+use crate::buffer::RawBuffer;
+
+const MAGIC: &[u8; 4] = b"RIDP";
+const VERSION: u8 = 1;
+
+fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn get_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos)?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn pack(ids: &[[u8; 16]]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(16 + ids.len() * 12);
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    put_varint(&mut out, ids.len() as u64);
+    let mut previous = 0u64;
+    for id in ids {
+        let hi = u64::from_be_bytes(id[..8].try_into().expect("8 bytes"));
+        let delta = hi.wrapping_sub(previous) as i64;
+        put_varint(&mut out, ((delta << 1) ^ (delta >> 63)) as u64);
+        out.extend_from_slice(&id[8..]);
+        previous = hi;
+    }
+    out
+}
+
+fn unpack(data: &[u8]) -> Result<Vec<[u8; 16]>, &'static str> {
+    if data.len() < 5 || &data[..4] != MAGIC {
+        return Err("not a packed ID buffer");
+    }
+    if data[4] != VERSION {
+        return Err("unsupported packed ID format version");
+    }
+    let mut pos = 5;
+    let count = get_varint(data, &mut pos).ok_or("truncated header")?;
+    // Every entry takes at least 9 bytes, which bounds a corrupt count.
+    if count > (data.len() / 9) as u64 {
+        return Err("count exceeds buffer size");
+    }
+    let mut ids = Vec::with_capacity(count as usize);
+    let mut previous = 0u64;
+    for _ in 0..count {
+        let zigzag = get_varint(data, &mut pos).ok_or("truncated entry")?;
+        let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+        let hi = previous.wrapping_add(delta as u64);
+        let lo = data.get(pos..pos + 8).ok_or("truncated entry")?;
+        pos += 8;
+        let mut id = [0u8; 16];
+        id[..8].copy_from_slice(&hi.to_be_bytes());
+        id[8..].copy_from_slice(lo);
+        ids.push(id);
+        previous = hi;
+    }
+    if pos != data.len() {
+        return Err("trailing bytes after last entry");
+    }
+    Ok(ids)
+}
+
+/// Encode IDs (a `UuidArray` or any iterable of IDs) for the wire. Order is
+/// preserved; sort time-ordered IDs first for the best compression.
+#[pyfunction]
+pub fn pack_ids<'py>(py: Python<'py>, ids: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    let ids = batch_bytes(ids)?;
+    let packed = py.allow_threads(|| pack(&ids));
+    Ok(PyBytes::new(py, &packed))
+}
+
+/// Decode a buffer from `pack_ids`; `returns` is as for the batch generators.
+#[pyfunction]
+#[pyo3(signature = (buf, returns="list"))]
+pub fn unpack_ids(py: Python<'_>, buf: &Bound<'_, PyAny>, returns: &str) -> PyResult<PyObject> {
+    let returns = Returns::parse(returns)?;
+    let buffer = RawBuffer::get(buf, false)?;
+    let ids = py
+        .allow_threads(|| unpack(buffer.as_slice()))
+        .map_err(|reason| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid packed IDs: {}", reason)))?;
+    returns.build(py, ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_sorted_v7() {
+        let mut ids: Vec<[u8; 16]> = (0..1000).map(|_| *uuid::Uuid::now_v7().as_bytes()).collect();
+        ids.sort();
+        let packed = pack(&ids);
+        assert!(packed.len() <= 8 + ids.len() * 12);
+        assert_eq!(unpack(&packed), Ok(ids));
+    }
+
+    #[test]
+    fn round_trips_unsorted_and_extreme_values() {
+        let mut ids: Vec<[u8; 16]> = (0..1000).map(|_| *uuid::Uuid::new_v4().as_bytes()).collect();
+        ids.extend([[0xff; 16], [0; 16], [0xff; 16], [0x80; 16], [0x7f; 16]]);
+        assert_eq!(unpack(&pack(&ids)), Ok(ids));
+        assert_eq!(unpack(&pack(&[])), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn rejects_corrupt_buffers() {
+        let ids: Vec<[u8; 16]> = (0..10).map(|_| *uuid::Uuid::now_v7().as_bytes()).collect();
+        let packed = pack(&ids);
+        for len in 0..packed.len() {
+            assert!(unpack(&packed[..len]).is_err(), "truncated to {} bytes", len);
+        }
+        let mut trailing = packed.clone();
+        trailing.push(0);
+        assert_eq!(unpack(&trailing), Err("trailing bytes after last entry"));
+        let mut version = packed;
+        version[4] = VERSION + 1;
+        assert_eq!(unpack(&version), Err("unsupported packed ID format version"));
+    }
+}
@@ -0,0 +1,121 @@
+//! Stable, storage-free human aliases for IDs, e.g. `brave-otter-42`, and
+//! display colors and identicon seeds.
+//!
+//! The mapping is part of the public contract: the word lists and the mixing
+//! function must never be reordered or changed, only new named lists added.
+
+use pyo3::prelude::*;
+
+use crate::hashing::{fold128, mix64};
+
+const ADJECTIVES: [&str; 128] = [
+    "able", "agile", "amber", "ample", "azure", "bold", "brave", "brief", "bright", "brisk", "calm",
+    "candid", "cheery", "chief", "civil", "clever", "cloudy", "cosmic", "crisp", "curious",
+    "dapper", "daring", "deft", "eager", "earnest", "easy", "elated", "epic", "exact", "fair",
+    "fancy", "fast", "fearless", "fiery", "fine", "fluent", "fond", "frank", "free", "fresh",
+    "frosty", "gentle", "giant", "glad", "golden", "grand", "great", "green", "happy", "hardy",
+    "hasty", "hearty", "helpful", "honest", "humble", "icy", "ideal", "jolly", "jovial", "keen",
+    "kind", "lively", "loyal", "lucid", "lucky", "lunar", "magic", "major", "mellow", "merry",
+    "mighty", "misty", "modest", "neat", "nimble", "noble", "polite", "proud", "quiet", "rapid",
+    "rare", "ready", "regal", "robust", "rosy", "royal", "rugged", "rustic", "safe", "sage",
+    "scarlet", "serene", "sharp", "silent", "silver", "simple", "sleek", "snowy", "solar", "solid",
+    "spry", "stable", "steady", "stellar", "stoic", "strong", "sturdy", "sunny", "super", "swift",
+    "tender", "tidy", "tough", "tranquil", "trusty", "upbeat", "urban", "valiant", "vast", "velvet",
+    "vivid", "warm", "wary", "wise", "witty", "young", "zany", "zesty",
+];
+
+const ANIMALS: [&str; 128] = [
+    "albatross", "alpaca", "ant", "antelope", "badger", "bat", "bear", "beaver", "bison", "buffalo",
+    "camel", "cheetah", "chipmunk", "coyote", "crab", "crane", "cricket", "deer", "dingo", "donkey",
+    "dove", "dragon", "duck", "eagle", "eel", "egret", "elk", "emu", "ferret", "finch", "flamingo",
+    "fox", "frog", "gazelle", "gecko", "gibbon", "giraffe", "goat", "goose", "gopher", "gorilla",
+    "grouse", "gull", "hamster", "hare", "hedgehog", "heron", "hippo", "hornet", "horse", "hound",
+    "ibis", "iguana", "impala", "jay", "kestrel", "kiwi", "koala", "lark", "lemur", "leopard",
+    "lion", "lizard", "llama", "lobster", "lynx", "magpie", "mamba", "manatee", "marmot", "marten",
+    "meerkat", "mink", "mole", "moose", "moth", "mouse", "mule", "narwhal", "newt", "ocelot",
+    "octopus", "okapi", "opossum", "orca", "osprey", "ostrich", "otter", "owl", "ox", "panda",
+    "panther", "parrot", "pelican", "penguin", "pheasant", "pigeon", "puma", "rabbit", "raven",
+    "salmon", "seal", "shark", "sheep", "shrew", "skunk", "sloth", "snail", "sparrow", "spider",
+    "squid", "stork", "tapir", "tiger", "toad", "toucan", "trout", "turkey", "turtle", "viper",
+    "vulture", "walrus", "weasel", "whale", "wolf", "wombat", "yak", "zebra",
+];
+
+/// Domain tags mixed into the `fold128` seed so colors, identicon seeds and
+/// aliases of the same ID are independent of each other.
+const COLOR_TAG: u64 = u64::from_be_bytes(*b"rid:colr");
+const IDENTICON_TAG: u64 = u64::from_be_bytes(*b"rid:icon");
+
+fn tagged_seed(bytes: &[u8; 16], tag: u64) -> u64 {
+    mix64(fold128(bytes) ^ tag)
+}
+
+fn alias(bytes: &[u8; 16], adjectives: &[impl AsRef<str>], nouns: &[impl AsRef<str>]) -> String {
+    let h = fold128(bytes);
+    let adjective = &adjectives[(h % adjectives.len() as u64) as usize];
+    let h = h / adjectives.len() as u64;
+    let noun = &nouns[(h % nouns.len() as u64) as usize];
+    let number = (h / nouns.len() as u64) % 100;
+    format!("{}-{}-{}", adjective.as_ref(), noun.as_ref(), number)
+}
+
+/// Alias of `bytes` from the default word lists.
+pub(crate) fn default_alias(bytes: &[u8; 16]) -> String {
+    alias(bytes, &ADJECTIVES, &ANIMALS)
+}
+
+/// Deterministic `adjective-noun-NN` alias for `id`.
+///
+/// `wordlist` is `"default"` or an `(adjectives, nouns)` pair of custom lists.
+/// Aliases are for display only: the default lists give ~1.6M combinations.
+#[pyfunction]
+#[pyo3(signature = (id, wordlist=None))]
+pub fn codename(id: &Bound<'_, PyAny>, wordlist: Option<&Bound<'_, PyAny>>) -> PyResult<String> {
+    let bytes = crate::uuid_bytes(id)?;
+    let wordlist = match wordlist {
+        None => return Ok(default_alias(&bytes)),
+        Some(wordlist) => wordlist,
+    };
+    if let Ok(name) = wordlist.extract::<&str>() {
+        return match name {
+            "default" => Ok(default_alias(&bytes)),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown wordlist: {}", name))),
+        };
+    }
+    let (adjectives, nouns): (Vec<String>, Vec<String>) = wordlist.extract()?;
+    if adjectives.is_empty() || nouns.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Word lists must not be empty"));
+    }
+    Ok(alias(&bytes, &adjectives, &nouns))
+}
+
+/// Deterministic display color for `id` as `#rrggbb`.
+///
+/// The hue is taken from a hash of the ID at 65% saturation and 50% lightness
+/// (HSL), so colors are evenly spread, equally readable on light and dark
+/// backgrounds, and not correlated with the ID's timestamp or its codename.
+#[pyfunction]
+pub fn to_color(id: &Bound<'_, PyAny>) -> PyResult<String> {
+    let hue = (tagged_seed(&crate::uuid_bytes(id)?, COLOR_TAG) % 360) as f64;
+    let (saturation, lightness) = (0.65, 0.5);
+    let chroma = (1.0 - (2.0 * lightness - 1.0_f64).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    Ok(format!("#{:02x}{:02x}{:02x}", channel(r), channel(g), channel(b)))
+}
+
+/// Deterministic 64-bit seed for identicon or avatar generators, e.g.
+/// `random.Random(to_identicon_seed(id))`, independent of `to_color` and
+/// `codename` for the same ID.
+#[pyfunction]
+pub fn to_identicon_seed(id: &Bound<'_, PyAny>) -> PyResult<u64> {
+    Ok(tagged_seed(&crate::uuid_bytes(id)?, IDENTICON_TAG))
+}
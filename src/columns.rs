@@ -0,0 +1,144 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+
+use crate::arrow::{ArrowIdStream, Utf8Table};
+use crate::alphabets::{url_safe_symbols, BASE36};
+use crate::audit;
+use crate::canonical_string;
+use crate::encoding::encode_u128;
+
+/// Whether `s` is a non-empty run of ASCII letters, digits and underscores,
+/// so it never needs quoting or escaping in COPY text or CSV output.
+fn is_plain_name(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// ID kind of a generated column, parsed from specs like `"uuid7"` or `"nanoid:12"`.
+#[derive(Clone, Debug)]
+pub(crate) enum ColumnKind {
+    Uuid4,
+    Uuid7,
+    ShortId,
+    NanoId(usize),
+    /// `prefix` followed by the fixed-width base36 form of a v7 UUID.
+    Prefixed(String),
+}
+
+impl ColumnKind {
+    pub(crate) fn parse(spec: &str) -> PyResult<Self> {
+        let (name, arg) = match spec.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (spec, None),
+        };
+        let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid column kind: {}", spec));
+        match (name, arg) {
+            ("uuid4", None) => Ok(ColumnKind::Uuid4),
+            ("uuid7", None) => Ok(ColumnKind::Uuid7),
+            ("short_id", None) => Ok(ColumnKind::ShortId),
+            ("nanoid", None) => Ok(ColumnKind::NanoId(21)),
+            ("nanoid", Some(size)) => size.parse().map(ColumnKind::NanoId).map_err(|_| invalid()),
+            ("prefixed", Some(prefix)) if is_plain_name(prefix) => Ok(ColumnKind::Prefixed(prefix.to_string())),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Generator name reported to the audit hook.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ColumnKind::Uuid4 => "uuid4",
+            ColumnKind::Uuid7 => "uuid7",
+            ColumnKind::ShortId => "short_id",
+            ColumnKind::NanoId(_) => "nano_id",
+            ColumnKind::Prefixed(_) => "prefixed",
+        }
+    }
+
+    fn value(&self) -> String {
+        match self {
+            ColumnKind::Uuid4 => canonical_string(uuid::Uuid::new_v4().as_bytes()),
+            ColumnKind::Uuid7 => canonical_string(uuid::Uuid::now_v7().as_bytes()),
+            ColumnKind::ShortId => URL_SAFE_NO_PAD.encode(&uuid::Uuid::now_v7().as_bytes()[0..12]),
+            ColumnKind::NanoId(size) => crate::nano_id_string(*size, url_safe_symbols(), true),
+            ColumnKind::Prefixed(prefix) => {
+                let id = u128::from_be_bytes(*uuid::Uuid::now_v7().as_bytes());
+                format!("{}{}", prefix, encode_u128(id, BASE36.as_bytes()))
+            }
+        }
+    }
+
+    /// Generate `count` raw 16-byte values, for kinds that are UUIDs.
+    pub(crate) fn generate_bytes(&self, count: usize) -> Option<Vec<[u8; 16]>> {
+        let make: fn() -> uuid::Uuid = match self {
+            ColumnKind::Uuid4 => uuid::Uuid::new_v4,
+            ColumnKind::Uuid7 => uuid::Uuid::now_v7,
+            _ => return None,
+        };
+        Some((0..count).into_par_iter().map(|_| *make().as_bytes()).collect())
+    }
+
+    /// Generate `count` values of this kind in parallel.
+    pub(crate) fn generate(&self, count: usize) -> Vec<String> {
+        (0..count).into_par_iter().map(|_| self.value()).collect()
+    }
+}
+
+/// Parse `[(name, kind), ...]` column specs. Names are limited to
+/// `[A-Za-z0-9_]`, like `"prefixed:"` prefixes.
+pub(crate) fn parse_columns(columns: Vec<(String, String)>) -> PyResult<Vec<(String, ColumnKind)>> {
+    if columns.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("At least one column is required"));
+    }
+    columns
+        .into_iter()
+        .map(|(name, spec)| {
+            if !is_plain_name(&name) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid column name {:?}: use only ASCII letters, digits and underscores",
+                    name
+                )));
+            }
+            Ok((name, ColumnKind::parse(&spec)?))
+        })
+        .collect()
+}
+
+/// Generate several ID columns of `count` rows in one call.
+///
+/// `columns` maps column names to kinds (`"uuid4"`, `"uuid7"`, `"short_id"`,
+/// `"nanoid[:size]"`, `"prefixed:<prefix>"`); names and prefixes may only
+/// use ASCII letters, digits and underscores. Returns a dict of lists, or an
+/// Arrow stream of one record batch when `format="arrow"`.
+#[pyfunction]
+#[pyo3(signature = (columns, count, format="dict"))]
+pub fn generate_columns<'py>(
+    py: Python<'py>,
+    columns: &Bound<'py, PyDict>,
+    count: usize,
+    format: &str,
+) -> PyResult<Bound<'py, PyAny>> {
+    let specs = columns
+        .iter()
+        .map(|(name, kind)| Ok((name.extract::<String>()?, kind.extract::<String>()?)))
+        .collect::<PyResult<Vec<_>>>()?;
+    let columns = parse_columns(specs)?;
+    if format != "dict" && format != "arrow" {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported format: {}", format)));
+    }
+    let values: Vec<Vec<String>> =
+        py.allow_threads(|| columns.par_iter().map(|(_, kind)| kind.generate(count)).collect());
+    for ((_, kind), column) in columns.iter().zip(&values) {
+        audit::record(py, kind.name(), column)?;
+    }
+    let names = columns.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+    if format == "arrow" {
+        let table = ArrowIdStream::new(Box::new(Utf8Table::new(names, values)));
+        return Ok(Bound::new(py, table)?.into_any());
+    }
+    let out = PyDict::new(py);
+    for (name, column) in names.into_iter().zip(values) {
+        out.set_item(name, column)?;
+    }
+    Ok(out.into_any())
+}
@@ -0,0 +1,109 @@
+//! Comparisons of IDs with each other and with literal values, ordering
+//! checks over sequences, and bit-level similarity for spotting RNG misuse
+//! such as identical generator state in forked workers.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use rayon::prelude::*;
+
+use crate::array::UuidArray;
+use crate::parse::parse_lenient;
+use crate::ulid::{parse_ulid, Ulid};
+use crate::UUID;
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// The value `other` stands for when compared with a `UUID`: another UUID, a
+/// stdlib UUID in stdlib hash mode, and, unless strict mode is on, a string
+/// in any form `parse_lenient` accepts or exactly 16 raw bytes. `None` means
+/// the comparison is not supported.
+pub(crate) fn comparable_bytes(other: &Bound<'_, PyAny>) -> PyResult<Option<[u8; 16]>> {
+    if let Ok(other) = other.downcast::<UUID>() {
+        return Ok(Some(other.get().bytes));
+    }
+    if !STRICT.load(Ordering::Relaxed) {
+        if let Ok(s) = other.downcast::<PyString>() {
+            return Ok(parse_lenient(s.to_str()?.as_bytes()).ok());
+        }
+        if let Ok(b) = other.downcast::<PyBytes>() {
+            return Ok(b.as_bytes().try_into().ok());
+        }
+    }
+    crate::hashing::stdlib_uuid_bytes(other)
+}
+
+/// With `enabled=True`, a `UUID` only equals other UUIDs, never `str` or
+/// `bytes` literals. Literal comparisons are a convenience for tests and
+/// config checks; note that a UUID and an equal string do not hash alike.
+#[pyfunction]
+#[pyo3(signature = (enabled=true))]
+pub fn set_strict_equality(enabled: bool) {
+    STRICT.store(enabled, Ordering::Relaxed);
+}
+
+/// Number of differing bits between two IDs (0..=128).
+#[pyfunction]
+pub fn distance(a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let a = u128::from_be_bytes(crate::uuid_bytes(a)?);
+    let b = u128::from_be_bytes(crate::uuid_bytes(b)?);
+    Ok((a ^ b).count_ones())
+}
+
+/// Number of leading bits two IDs share (0..=128).
+#[pyfunction]
+pub fn prefix_match_len(a: &Bound<'_, PyAny>, b: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let a = u128::from_be_bytes(crate::uuid_bytes(a)?);
+    let b = u128::from_be_bytes(crate::uuid_bytes(b)?);
+    Ok((a ^ b).leading_zeros())
+}
+
+/// Sort key of an item in an ordering check: the 128-bit value of a UUID or
+/// ULID (object or string), else the raw text or bytes, which orders KSUIDs
+/// and other fixed-width sortable IDs.
+fn order_key(item: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(ulid) = item.downcast::<Ulid>() {
+        return Ok(ulid.get().bytes.to_vec());
+    }
+    if let Ok(s) = item.downcast::<PyString>() {
+        let s = s.to_str()?.as_bytes();
+        return Ok(parse_lenient(s).or_else(|_| parse_ulid(s)).map_or_else(|_| s.to_vec(), |bytes| bytes.to_vec()));
+    }
+    if let Ok(b) = item.downcast::<PyBytes>() {
+        return Ok(b.as_bytes().to_vec());
+    }
+    Ok(crate::uuid_bytes(item)?.to_vec())
+}
+
+/// Index of the first key that does not come after its predecessor.
+fn violation<T: Ord + Sync>(keys: &[T], strict: bool) -> Option<usize> {
+    keys.par_windows(2)
+        .position_first(|pair| if strict { pair[0] >= pair[1] } else { pair[0] > pair[1] })
+        .map(|i| i + 1)
+}
+
+/// Index of the first ID in `ids` that is not greater than the one before
+/// it, or `None` if the sequence is strictly increasing; with
+/// `strict=False` repeated IDs are allowed.
+///
+/// Items may be v7 (or any) UUIDs and ULIDs, as objects or strings, or
+/// KSUIDs and other IDs whose text or bytes sort in creation order. The
+/// pairs are compared in parallel.
+#[pyfunction]
+#[pyo3(signature = (ids, strict=true))]
+pub fn first_violation(py: Python<'_>, ids: &Bound<'_, PyAny>, strict: bool) -> PyResult<Option<usize>> {
+    if let Ok(array) = ids.downcast::<UuidArray>() {
+        let keys = &array.borrow().ids;
+        return Ok(py.allow_threads(|| violation(keys, strict)));
+    }
+    let keys = ids.try_iter()?.map(|item| order_key(&item?)).collect::<PyResult<Vec<_>>>()?;
+    Ok(py.allow_threads(|| violation(&keys, strict)))
+}
+
+/// Whether `ids` is increasing; see `first_violation`.
+#[pyfunction]
+#[pyo3(signature = (ids, strict=true))]
+pub fn is_monotonic(py: Python<'_>, ids: &Bound<'_, PyAny>, strict: bool) -> PyResult<bool> {
+    Ok(first_violation(py, ids, strict)?.is_none())
+}
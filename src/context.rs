@@ -0,0 +1,49 @@
+//! Request IDs carried in a `contextvars.ContextVar`, for ASGI/asyncio middleware.
+
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+
+use crate::audit;
+use crate::canonical_string;
+
+static REQUEST_ID: GILOnceCell<Py<PyAny>> = GILOnceCell::new();
+
+fn request_id_var(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    REQUEST_ID
+        .get_or_try_init(py, || {
+            let var = py.import("contextvars")?.getattr("ContextVar")?;
+            let kwargs = pyo3::types::PyDict::new(py);
+            kwargs.set_item("default", py.None())?;
+            Ok::<_, PyErr>(var.call(("rustid_request_id",), Some(&kwargs))?.unbind())
+        })
+        .map(|var| var.bind(py))
+}
+
+/// Generate a v7 request ID, make it the current one for this context, and return it.
+#[pyfunction]
+pub fn new_request_id(py: Python<'_>) -> PyResult<String> {
+    let id = canonical_string(uuid::Uuid::now_v7().as_bytes());
+    audit::record(py, "request_id", [&id])?;
+    request_id_var(py)?.call_method1("set", (&id,))?;
+    Ok(id)
+}
+
+/// The request ID of the current context, or `None` outside a request.
+#[pyfunction]
+pub fn current_request_id(py: Python<'_>) -> PyResult<Option<String>> {
+    request_id_var(py)?.call_method0("get")?.extract()
+}
+
+/// Use an existing ID (e.g. from an `X-Request-ID` header) as the current
+/// one; returns a token for `reset_request_id`.
+#[pyfunction]
+pub fn set_request_id<'py>(py: Python<'py>, request_id: &str) -> PyResult<Bound<'py, PyAny>> {
+    request_id_var(py)?.call_method1("set", (request_id,))
+}
+
+/// Restore the request ID that was current before `set_request_id`.
+#[pyfunction]
+pub fn reset_request_id(py: Python<'_>, token: &Bound<'_, PyAny>) -> PyResult<()> {
+    request_id_var(py)?.call_method1("reset", (token,))?;
+    Ok(())
+}
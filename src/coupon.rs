@@ -0,0 +1,158 @@
+//! Coupon codes: short random codes for people to type, drawn from the OS
+//! CSPRNG with look-alike characters left out.
+
+use std::collections::HashSet;
+
+use pyo3::prelude::*;
+
+use crate::audit;
+use crate::checkdigit::Checker;
+use crate::secure_below;
+
+/// Symbols before exclusions: digits and uppercase letters.
+const SYMBOLS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Marks a random symbol in a pattern; every other character is literal.
+const PLACEHOLDER: char = 'X';
+
+fn default_exclude() -> Vec<String> {
+    ["0", "O", "I", "1"].iter().map(|s| s.to_string()).collect()
+}
+
+fn value_error(msg: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(msg)
+}
+
+struct CouponFormat {
+    pattern: Vec<char>,
+    symbols: Vec<char>,
+    checker: Option<Checker>,
+}
+
+impl CouponFormat {
+    fn new(pattern: &str, exclude: &[String], check: Option<&str>) -> PyResult<Self> {
+        let symbols: String = SYMBOLS.chars().filter(|&c| !exclude.iter().any(|e| e.contains(c))).collect();
+        if symbols.len() < 2 {
+            return Err(value_error("exclude leaves fewer than 2 coupon code symbols".to_string()));
+        }
+        let pattern: Vec<char> = pattern.chars().collect();
+        if !pattern.contains(&PLACEHOLDER) {
+            return Err(value_error(format!("Coupon pattern has no {:?} placeholder", PLACEHOLDER)));
+        }
+        let checker = check.map(|scheme| Checker::new(scheme, Some(&symbols))).transpose()?;
+        Ok(CouponFormat { pattern, symbols: symbols.chars().collect(), checker })
+    }
+
+    /// Number of distinct codes, saturating at `u128::MAX`.
+    fn capacity(&self) -> u128 {
+        let slots = self.pattern.iter().filter(|&&c| c == PLACEHOLDER).count();
+        (self.symbols.len() as u128).checked_pow(slots as u32).unwrap_or(u128::MAX)
+    }
+
+    fn generate(&self) -> String {
+        let mut digits = Vec::with_capacity(self.pattern.len());
+        let mut code = String::with_capacity(self.pattern.len() + 1);
+        for &c in &self.pattern {
+            if c == PLACEHOLDER {
+                let digit = secure_below(self.symbols.len() as u32);
+                digits.push(digit);
+                code.push(self.symbols[digit as usize]);
+            } else {
+                code.push(c);
+            }
+        }
+        if let Some(checker) = &self.checker {
+            code.push(self.symbols[checker.check_digit(&digits) as usize]);
+        }
+        code
+    }
+
+    fn digit(&self, c: char) -> Option<u32> {
+        self.symbols.iter().position(|&s| s == c).map(|d| d as u32)
+    }
+
+    fn is_valid(&self, code: &str) -> bool {
+        let code: Vec<char> = code.to_uppercase().chars().collect();
+        if code.len() != self.pattern.len() + self.checker.is_some() as usize {
+            return false;
+        }
+        let mut digits = Vec::with_capacity(code.len());
+        for (&p, &c) in self.pattern.iter().zip(&code) {
+            if p == PLACEHOLDER {
+                match self.digit(c) {
+                    Some(d) => digits.push(d),
+                    None => return false,
+                }
+            } else if !p.eq_ignore_ascii_case(&c) {
+                return false;
+            }
+        }
+        match &self.checker {
+            Some(checker) => match self.digit(code[code.len() - 1]) {
+                Some(d) => {
+                    digits.push(d);
+                    checker.is_valid(&digits)
+                }
+                None => false,
+            },
+            None => true,
+        }
+    }
+}
+
+/// Random coupon code shaped like `pattern`, where each `X` becomes a random
+/// digit or uppercase letter and other characters are kept as they are.
+///
+/// Characters in any `exclude` entry are never used. With `check` set to a
+/// check digit scheme (`"damm"`, `"luhn"` or `"verhoeff"`, as for
+/// `add_check_digit`), a check character over the random symbols is
+/// appended, so `is_valid_coupon_code` catches most typos.
+#[pyfunction]
+#[pyo3(signature = (pattern="XXXX-XXXX", exclude=default_exclude(), check=None))]
+pub fn coupon_code(py: Python<'_>, pattern: &str, exclude: Vec<String>, check: Option<&str>) -> PyResult<String> {
+    let code = CouponFormat::new(pattern, &exclude, check)?.generate();
+    audit::record(py, "coupon_code", [&code])?;
+    Ok(code)
+}
+
+/// `count` distinct coupon codes; raises `ValueError` if the pattern has
+/// fewer than `count` possible codes.
+#[pyfunction]
+#[pyo3(signature = (count, pattern="XXXX-XXXX", exclude=default_exclude(), check=None))]
+pub fn coupon_code_batch(
+    py: Python<'_>,
+    count: usize,
+    pattern: &str,
+    exclude: Vec<String>,
+    check: Option<&str>,
+) -> PyResult<Vec<String>> {
+    let format = CouponFormat::new(pattern, &exclude, check)?;
+    if count as u128 > format.capacity() {
+        return Err(value_error(format!(
+            "Pattern {:?} allows only {} distinct coupon codes, {} requested",
+            pattern,
+            format.capacity(),
+            count
+        )));
+    }
+    let codes: Vec<String> = py.allow_threads(|| {
+        let mut seen = HashSet::with_capacity(count);
+        let mut codes = Vec::with_capacity(count);
+        while codes.len() < count {
+            let code = format.generate();
+            if seen.insert(code.clone()) {
+                codes.push(code);
+            }
+        }
+        codes
+    });
+    audit::record(py, "coupon_code", &codes)?;
+    Ok(codes)
+}
+
+/// Whether `code` matches `pattern` with symbols outside `exclude` and, when
+/// `check` is set, a valid check character. Letters match case-insensitively.
+#[pyfunction]
+#[pyo3(signature = (code, pattern="XXXX-XXXX", exclude=default_exclude(), check=None))]
+pub fn is_valid_coupon_code(code: &str, pattern: &str, exclude: Vec<String>, check: Option<&str>) -> PyResult<bool> {
+    Ok(CouponFormat::new(pattern, &exclude, check)?.is_valid(code))
+}
@@ -0,0 +1,115 @@
+//! CUID2 identifiers, compatible with the `@paralleldrive/cuid2` reference
+//! implementation: a random letter followed by the base36 SHA3-512 hash of
+//! the time, a random salt, a session counter, and a host fingerprint.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use tiny_keccak::{Hasher, Sha3};
+
+use crate::alphabets::BASE36;
+use crate::audit;
+use crate::encoding::convert_base;
+use crate::monotonic::now_ms;
+use crate::{dedupe, secure_below};
+
+const DEFAULT_LENGTH: usize = 24;
+const MIN_LENGTH: usize = 2;
+const MAX_LENGTH: usize = 32;
+/// The reference implementation seeds its counter below this value.
+const INITIAL_COUNT_MAX: u32 = 476_782_367;
+
+/// `length` random base36 characters.
+fn entropy(length: usize) -> String {
+    (0..length).map(|_| BASE36.as_bytes()[secure_below(36) as usize] as char).collect()
+}
+
+fn to_base36(value: u64) -> String {
+    let digits: Vec<u32> = value.to_be_bytes().iter().map(|&b| b as u32).collect();
+    base36_string(&digits)
+}
+
+/// Base36 of a big-endian base-256 number, without leading zeros like
+/// JavaScript's `BigInt.prototype.toString(36)`.
+fn base36_string(digits: &[u32]) -> String {
+    let out: String = convert_base(digits, 256, 36)
+        .iter()
+        .skip_while(|&&d| d == 0)
+        .map(|&d| BASE36.as_bytes()[d as usize] as char)
+        .collect();
+    if out.is_empty() { "0".to_string() } else { out }
+}
+
+/// The reference `hash()`: SHA3-512 as a base36 big integer, minus its first
+/// (least uniformly distributed) digit.
+fn hash(input: &str) -> String {
+    let mut sha3 = Sha3::v512();
+    sha3.update(input.as_bytes());
+    let mut digest = [0u8; 64];
+    sha3.finalize(&mut digest);
+    let digits: Vec<u32> = digest.iter().map(|&b| b as u32).collect();
+    base36_string(&digits)[1..].to_string()
+}
+
+fn fingerprint() -> &'static str {
+    static FINGERPRINT: OnceLock<String> = OnceLock::new();
+    FINGERPRINT.get_or_init(|| {
+        let mut fp = hash(&format!("{}{}", std::process::id(), entropy(MAX_LENGTH)));
+        fp.truncate(MAX_LENGTH);
+        fp
+    })
+}
+
+fn next_count() -> u64 {
+    static COUNTER: OnceLock<AtomicU64> = OnceLock::new();
+    COUNTER
+        .get_or_init(|| AtomicU64::new(secure_below(INITIAL_COUNT_MAX) as u64))
+        .fetch_add(1, Ordering::Relaxed)
+}
+
+fn generate(length: usize) -> String {
+    let first = BASE36.as_bytes()[10 + secure_below(26) as usize] as char;
+    let input = format!("{}{}{}{}", to_base36(now_ms()), entropy(length), to_base36(next_count()), fingerprint());
+    let mut id = String::with_capacity(length);
+    id.push(first);
+    id.push_str(&hash(&input)[1..length]);
+    id
+}
+
+fn check_length(length: Option<usize>) -> PyResult<usize> {
+    let length = length.unwrap_or(DEFAULT_LENGTH);
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "CUID2 length must be between {} and {}",
+            MIN_LENGTH, MAX_LENGTH
+        )));
+    }
+    Ok(length)
+}
+
+/// CUID2 of `length` characters (default 24, 2 to 32): a lowercase letter
+/// followed by base36 digits.
+#[pyfunction]
+#[pyo3(signature = (length=None))]
+pub fn cuid2(py: Python<'_>, length: Option<usize>) -> PyResult<String> {
+    let id = generate(check_length(length)?);
+    audit::record(py, "cuid2", [&id])?;
+    Ok(id)
+}
+
+#[pyfunction]
+#[pyo3(signature = (count, length=None, unique=false))]
+pub fn cuid2_batch(py: Python<'_>, count: usize, length: Option<usize>, unique: bool) -> PyResult<Vec<String>> {
+    let length = check_length(length)?;
+    let ids: Vec<String> = py.allow_threads(|| {
+        let mut ids: Vec<String> = (0..count).into_par_iter().map(|_| generate(length)).collect();
+        if unique {
+            dedupe(&mut ids, || generate(length));
+        }
+        ids
+    });
+    audit::record(py, "cuid2", &ids)?;
+    Ok(ids)
+}
@@ -0,0 +1,164 @@
+//! Deterministic IDs derived from caller-held keys with HKDF-SHA256.
+
+use hkdf::Hkdf;
+use hmac::{Hmac, KeyInit, Mac};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyBytes, PyInt, PyString, PyTuple};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::UUID;
+
+/// Key material from `str` (UTF-8) or any bytes-like object.
+pub(crate) fn key_bytes(key: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(s) = key.downcast::<PyString>() {
+        return Ok(s.to_str()?.as_bytes().to_vec());
+    }
+    key.extract::<Vec<u8>>()
+}
+
+pub(crate) const DERIVE_UUID_SALT: &[u8] = b"rustid.derive_uuid";
+
+/// HKDF instance for `key`, domain-separated by `salt`.
+pub(crate) fn hkdf(key: &[u8], salt: &[u8]) -> Hkdf<Sha256> {
+    Hkdf::<Sha256>::new(Some(salt), key)
+}
+
+/// 16 output bytes for `info`.
+pub(crate) fn expand16(hk: &Hkdf<Sha256>, info: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    hk.expand(info, &mut out).expect("16 bytes is a valid HKDF output length");
+    out
+}
+
+/// `n` deterministic v7-shaped UUIDs for `key`, starting at sequence index `start`.
+///
+/// No clock is read: index `i` gets `unix_ts_ms = epoch_ms + i`, so the
+/// sequence sorts by index, and `rand_a`/`rand_b` come from
+/// HKDF-SHA256(key, info = big-endian `i`). The same key always yields the
+/// same sequence, and any window of it can be regenerated with `start`.
+#[pyfunction]
+#[pyo3(signature = (key, n, start=0, epoch_ms=1_577_836_800_000))]
+pub fn uuid7_seq(py: Python<'_>, key: &Bound<'_, PyAny>, n: u64, start: u64, epoch_ms: u64) -> PyResult<Vec<UUID>> {
+    let hk = hkdf(&key_bytes(key)?, b"rustid.uuid7_seq");
+    Ok(py.allow_threads(|| {
+        (start..start + n)
+            .into_par_iter()
+            .map(|i| {
+                let mut bytes = expand16(&hk, &i.to_be_bytes());
+                let ms = epoch_ms.wrapping_add(i) & 0xffff_ffff_ffff;
+                bytes[0..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+                bytes[6] = (bytes[6] & 0x0f) | 0x70;
+                bytes[8] = (bytes[8] & 0x3f) | 0x80;
+                UUID { bytes }
+            })
+            .collect()
+    }))
+}
+
+/// `n` v4-shaped UUIDs derived from a shared `seed`, for pre-assigning IDs
+/// across services before the resources exist.
+///
+/// ID `i` is HKDF-SHA256(seed, info = `info` || big-endian `i`) with the v4
+/// bits set: every holder of the seed computes the same list for the same
+/// `info`, while to anyone else the IDs are indistinguishable from `uuid4()`.
+/// Use a distinct `info` per purpose; `start` regenerates a later window.
+#[pyfunction]
+#[pyo3(signature = (seed, info, n, start=0))]
+pub fn derive_uuid(
+    py: Python<'_>,
+    seed: &Bound<'_, PyAny>,
+    info: &Bound<'_, PyAny>,
+    n: u64,
+    start: u64,
+) -> PyResult<Vec<UUID>> {
+    let hk = hkdf(&key_bytes(seed)?, DERIVE_UUID_SALT);
+    let info = key_bytes(info)?;
+    Ok(py.allow_threads(|| {
+        (start..start + n)
+            .into_par_iter()
+            .map(|i| UUID { bytes: derived_bytes(&hk, &info, i) })
+            .collect()
+    }))
+}
+
+/// ID `i` of `derive_uuid` for the HKDF instance `hk` of the seed.
+pub(crate) fn derived_bytes(hk: &Hkdf<Sha256>, info: &[u8], i: u64) -> [u8; 16] {
+    let mut message = info.to_vec();
+    message.extend_from_slice(&i.to_be_bytes());
+    let mut bytes = expand16(hk, &message);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    bytes
+}
+
+fn set_v8(bytes: &mut [u8; 16]) {
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+}
+
+/// v8 UUID from the first 128 bits of HMAC-SHA256(`key`, `message`).
+pub(crate) fn hmac_v8(key: &[u8], message: &[u8]) -> [u8; 16] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&mac.finalize().into_bytes()[..16]);
+    set_v8(&mut bytes);
+    bytes
+}
+
+/// Append `data` with a type tag and length so distinct part lists never
+/// serialize to the same bytes (`("ab", "c")` vs `("a", "bc")`, `1` vs `"1"`).
+fn push_tagged(out: &mut Vec<u8>, tag: u8, data: &[u8]) {
+    out.push(tag);
+    out.extend_from_slice(&(data.len() as u64).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+fn push_part(out: &mut Vec<u8>, part: &Bound<'_, PyAny>) -> PyResult<()> {
+    if part.is_none() {
+        push_tagged(out, b'n', b"");
+    } else if let Ok(b) = part.downcast::<PyBool>() {
+        push_tagged(out, b'?', &[b.is_true() as u8]);
+    } else if let Ok(i) = part.downcast::<PyInt>() {
+        push_tagged(out, b'i', i.str()?.to_str()?.as_bytes());
+    } else if let Ok(s) = part.downcast::<PyString>() {
+        push_tagged(out, b's', s.to_str()?.as_bytes());
+    } else if let Ok(b) = part.downcast::<PyBytes>() {
+        push_tagged(out, b'b', b.as_bytes());
+    } else if let Ok(id) = crate::uuid_bytes(part) {
+        push_tagged(out, b'u', &id);
+    } else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
+            "Unsupported idempotency key part: {}",
+            part.get_type().name()?
+        )));
+    }
+    Ok(())
+}
+
+/// Deterministic v8 UUID for a request, derived from `scope` and `parts`.
+///
+/// Parts may be str, bytes, int, bool, None, or UUIDs. The ID is the first
+/// 128 bits of SHA-256 over the tagged parts, or of HMAC-SHA256 under `key`
+/// when given, so independent services compute the same key for the same
+/// request and outsiders without the key cannot predict it.
+#[pyfunction]
+#[pyo3(signature = (scope, *parts, key=None))]
+pub fn idempotency_key(scope: &str, parts: &Bound<'_, PyTuple>, key: Option<&Bound<'_, PyAny>>) -> PyResult<UUID> {
+    let mut message = b"rustid.idempotency_key".to_vec();
+    push_tagged(&mut message, b's', scope.as_bytes());
+    for part in parts.iter() {
+        push_part(&mut message, &part)?;
+    }
+    let bytes = match key {
+        Some(key) => hmac_v8(&key_bytes(key)?, &message),
+        None => {
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(&Sha256::digest(&message)[..16]);
+            set_v8(&mut bytes);
+            bytes
+        }
+    };
+    Ok(UUID { bytes })
+}
@@ -0,0 +1,214 @@
+//! Fixed-width positional encodings of the 128-bit UUID value.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+/// Number of digits needed to represent any `u128` in `base`.
+fn width_for(base: u128) -> usize {
+    let mut width = 1;
+    let mut max = u128::MAX;
+    while max >= base {
+        max /= base;
+        width += 1;
+    }
+    width
+}
+
+/// Encode `value` with `alphabet`, left-padded with the zero digit to a fixed
+/// width so encodings sort in the same order as the values.
+pub(crate) fn encode_u128(mut value: u128, alphabet: &[u8]) -> String {
+    let base = alphabet.len() as u128;
+    let mut out = vec![alphabet[0]; width_for(base)];
+    for slot in out.iter_mut().rev() {
+        *slot = alphabet[(value % base) as usize];
+        value /= base;
+    }
+    String::from_utf8(out).expect("alphabets are ASCII")
+}
+
+/// Decode a string produced by `encode_u128`; `digit` maps a byte to its value.
+pub(crate) fn decode_u128(s: &str, base: u128, digit: impl Fn(u8) -> Option<u8>, name: &str) -> PyResult<u128> {
+    if s.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Empty {} string", name)));
+    }
+    let mut value: u128 = 0;
+    for &c in s.as_bytes() {
+        let d = digit(c).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid {} character: {:?}", name, c as char))
+        })?;
+        value = value
+            .checked_mul(base)
+            .and_then(|v| v.checked_add(d as u128))
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{} value exceeds 128 bits", name)))?;
+    }
+    Ok(value)
+}
+
+pub(crate) fn base36_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'z' => Some(c - b'a' + 10),
+        b'A'..=b'Z' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Crockford base32 digit, case-insensitive, reading `I`/`L` as 1 and `O` as 0.
+pub(crate) fn crockford_digit(c: u8) -> Option<u8> {
+    match c.to_ascii_uppercase() {
+        c @ b'0'..=b'9' => Some(c - b'0'),
+        b'O' => Some(0),
+        b'I' | b'L' => Some(1),
+        c @ b'A'..=b'Z' => crate::alphabets::CROCKFORD32.bytes().position(|a| a == c).map(|i| i as u8),
+        _ => None,
+    }
+}
+
+/// Bitcoin base58 digit; case-sensitive, with no aliases for the omitted symbols.
+pub(crate) fn base58_digit(c: u8) -> Option<u8> {
+    crate::alphabets::BASE58.bytes().position(|a| a == c).map(|i| i as u8)
+}
+
+/// A custom digit alphabet given as a Python string.
+pub(crate) struct Alphabet {
+    symbols: Vec<char>,
+    lookup: HashMap<char, u32>,
+}
+
+/// Code points that do not stand alone as a visible symbol: they attach to
+/// or modify their neighbour (combining marks, variation selectors, emoji
+/// modifiers and joiners) or are invisible (controls, whitespace, format
+/// characters), so an alphabet using them would yield IDs whose length and
+/// rendering depend on what surrounds each symbol.
+fn unsafe_symbol(c: char) -> Option<&'static str> {
+    match c as u32 {
+        _ if c.is_control() => Some("a control character"),
+        _ if c.is_whitespace() => Some("whitespace"),
+        0xFE00..=0xFE0F | 0xE0100..=0xE01EF => Some("a variation selector"),
+        _ if crate::marks::is_mark(c) => Some("a combining mark"),
+        0x1F3FB..=0x1F3FF => Some("an emoji modifier"),
+        0x1F1E6..=0x1F1FF => Some("a regional indicator"),
+        0xE0020..=0xE007F => Some("a tag character"),
+        0x00AD | 0x200B..=0x200F | 0x2028..=0x202E | 0x2060..=0x206F | 0xFEFF => Some("an invisible format character"),
+        _ => None,
+    }
+}
+
+impl Alphabet {
+    /// Validate and index `alphabet`: at least two distinct symbols, each a
+    /// single code point that renders on its own.
+    pub(crate) fn new(alphabet: &str) -> PyResult<Self> {
+        let symbols: Vec<char> = alphabet.chars().collect();
+        if symbols.len() < 2 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Alphabet needs at least 2 symbols"));
+        }
+        if let Some((i, c, what)) = symbols.iter().enumerate().find_map(|(i, &c)| unsafe_symbol(c).map(|what| (i, c, what))) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Alphabet symbol {} (U+{:04X}) is {}; use single, standalone characters",
+                i, c as u32, what
+            )));
+        }
+        let mut lookup: HashMap<char, u32> = HashMap::with_capacity(symbols.len());
+        for (i, &c) in symbols.iter().enumerate() {
+            if let Some(first) = lookup.insert(c, i as u32) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Alphabet contains duplicate symbol {:?} at positions {} and {}",
+                    c, first, i
+                )));
+            }
+        }
+        Ok(Alphabet { symbols, lookup })
+    }
+
+    pub(crate) fn base(&self) -> u32 {
+        self.symbols.len() as u32
+    }
+
+    pub(crate) fn symbols(&self) -> &[char] {
+        &self.symbols
+    }
+
+    pub(crate) fn symbol(&self, digit: u32) -> char {
+        self.symbols[digit as usize]
+    }
+
+    pub(crate) fn digits(&self, s: &str) -> PyResult<Vec<u32>> {
+        s.chars()
+            .map(|c| {
+                self.lookup.get(&c).copied().ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Character {:?} is not in the alphabet", c))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Convert big-endian `digits` from base `from` to base `to`, keeping one
+/// leading zero digit for each leading zero digit of the input.
+pub(crate) fn convert_base(digits: &[u32], from: u32, to: u32) -> Vec<u32> {
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    let mut number: Vec<u32> = digits[zeros..].to_vec();
+    let mut out = Vec::new();
+    while !number.is_empty() {
+        let mut quotient = Vec::with_capacity(number.len());
+        let mut remainder: u64 = 0;
+        for &d in &number {
+            let acc = remainder * from as u64 + d as u64;
+            let q = (acc / to as u64) as u32;
+            remainder = acc % to as u64;
+            if q != 0 || !quotient.is_empty() {
+                quotient.push(q);
+            }
+        }
+        out.push(remainder as u32);
+        number = quotient;
+    }
+    out.extend(std::iter::repeat_n(0, zeros));
+    out.reverse();
+    out
+}
+
+/// Re-encode `s` from one digit alphabet to another by exact base conversion.
+///
+/// Leading zero symbols are preserved one-for-one (as in base58), so
+/// `recode(recode(s, a, b), b, a) == s` for any valid `s`.
+#[pyfunction]
+pub fn recode(s: &str, from_alphabet: &str, to_alphabet: &str) -> PyResult<String> {
+    let from = Alphabet::new(from_alphabet)?;
+    let to = Alphabet::new(to_alphabet)?;
+    let digits = from.digits(s)?;
+    Ok(convert_base(&digits, from.base(), to.base()).into_iter().map(|d| to.symbol(d)).collect())
+}
+
+/// Encode an ID as a fixed-width string in `alphabet`, e.g. `alphabets.BASE58`.
+/// Encodings have the same width for every ID and sort in byte order.
+#[pyfunction]
+pub fn encode(id: &Bound<'_, PyAny>, alphabet: &str) -> PyResult<String> {
+    let alphabet = Alphabet::new(alphabet)?;
+    let value = u128::from_be_bytes(crate::uuid_bytes(id)?);
+    let mut digits = vec![0u32; width_for(alphabet.base() as u128)];
+    let mut rest = value;
+    for digit in digits.iter_mut().rev() {
+        *digit = (rest % alphabet.base() as u128) as u32;
+        rest /= alphabet.base() as u128;
+    }
+    Ok(digits.into_iter().map(|d| alphabet.symbol(d)).collect())
+}
+
+/// Decode a string produced by `encode` with the same alphabet.
+#[pyfunction]
+pub fn decode(s: &str, alphabet: &str) -> PyResult<crate::UUID> {
+    let alphabet = Alphabet::new(alphabet)?;
+    if s.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Empty string"));
+    }
+    let mut value: u128 = 0;
+    for digit in alphabet.digits(s)? {
+        value = value
+            .checked_mul(alphabet.base() as u128)
+            .and_then(|v| v.checked_add(digit as u128))
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Value exceeds 128 bits"))?;
+    }
+    Ok(crate::UUID { bytes: value.to_be_bytes() })
+}
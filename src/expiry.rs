@@ -0,0 +1,67 @@
+//! Self-expiring IDs: v8 UUIDs that carry their own expiry time, for tokens
+//! that need no storage to check whether they are still valid.
+//!
+//! The layout mirrors v7 with the expiry in place of the creation time:
+//! bits 0-47 hold the expiry in Unix milliseconds (big-endian), followed by
+//! the version (`8`), 12 random bits, the RFC variant and 62 random bits.
+//! IDs therefore sort by expiry. The layout is not marked beyond the
+//! version, so the accessors read any v8 UUID this way, and nothing stops a
+//! client from minting an ID with a later expiry: check authenticity
+//! separately, e.g. by looking the ID up or signing it.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDateTime, PyTzInfo};
+
+use crate::audit;
+use crate::monotonic::now_ms;
+use crate::shard::duration_ms;
+use crate::UUID;
+
+/// Largest expiry the 48-bit field holds (in the year 10889).
+const MAX_EXPIRY_MS: u64 = (1 << 48) - 1;
+
+pub(crate) fn expiring_bytes(expiry_ms: u64) -> [u8; 16] {
+    let mut bytes = *uuid::Uuid::new_v4().as_bytes();
+    bytes[..6].copy_from_slice(&expiry_ms.to_be_bytes()[2..]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x80;
+    bytes
+}
+
+fn expiry_ms(id: &Bound<'_, PyAny>) -> PyResult<u64> {
+    let bytes = crate::uuid_bytes(id)?;
+    let version = bytes[6] >> 4;
+    if version != 8 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "UUID version {} is not an expiring ID; only version 8 is",
+            version
+        )));
+    }
+    Ok(u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]))
+}
+
+/// New v8 UUID that expires `ttl` from now; `ttl` is a string such as
+/// `"15m"` or `"7d"` (units `ms`, `s`, `m`, `h`, `d`, `w`), an int of
+/// milliseconds, or a `timedelta`.
+#[pyfunction]
+pub fn expiring_id(py: Python<'_>, ttl: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let expiry = now_ms()
+        .checked_add(duration_ms(ttl, "ttl")?)
+        .filter(|&ms| ms <= MAX_EXPIRY_MS)
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyOverflowError, _>("ttl is too large"))?;
+    let id = UUID { bytes: expiring_bytes(expiry) };
+    audit::record(py, "expiring_id", [id])?;
+    Ok(id)
+}
+
+/// Expiry of an `expiring_id` as an aware UTC `datetime`.
+#[pyfunction]
+pub fn expires_at<'py>(py: Python<'py>, id: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyDateTime>> {
+    let utc = PyTzInfo::utc(py)?;
+    PyDateTime::from_timestamp(py, expiry_ms(id)? as f64 / 1000.0, Some(&utc))
+}
+
+/// Whether the expiry of an `expiring_id` has been reached.
+#[pyfunction]
+pub fn is_expired(id: &Bound<'_, PyAny>) -> PyResult<bool> {
+    Ok(now_ms() >= expiry_ms(id)?)
+}
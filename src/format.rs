@@ -0,0 +1,106 @@
+//! Bulk formatting of existing IDs, display grouping of tokens, and the
+//! process-wide letter case of hex output.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use crate::array::batch_bytes;
+use crate::{canonical_string, encode_canonical};
+
+static UPPERCASE: AtomicBool = AtomicBool::new(false);
+
+/// Whether hex digits in formatted IDs are uppercase.
+pub(crate) fn uppercase() -> bool {
+    UPPERCASE.load(Ordering::Relaxed)
+}
+
+/// With `enabled=True`, every canonical or hex ID string this module emits
+/// uses uppercase digits: `str()`, `UUID.hex`, batch formatting, request IDs,
+/// and the text written by `write_copy`, Arrow streams, and
+/// `pseudonymize_file`. Parsing accepts either case regardless.
+#[pyfunction]
+#[pyo3(signature = (enabled=true))]
+pub fn set_uppercase(enabled: bool) {
+    if UPPERCASE.swap(enabled, Ordering::Relaxed) != enabled {
+        crate::intern::clear();
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Style {
+    Canonical,
+    Hex,
+    Urn,
+    Base64Url,
+}
+
+impl Style {
+    fn parse(style: &str) -> PyResult<Self> {
+        match style {
+            "canonical" => Ok(Style::Canonical),
+            "hex" => Ok(Style::Hex),
+            "urn" => Ok(Style::Urn),
+            "base64url" => Ok(Style::Base64Url),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported format style: {}", style))),
+        }
+    }
+
+    fn format(self, bytes: &[u8; 16]) -> String {
+        match self {
+            Style::Canonical => canonical_string(bytes),
+            Style::Hex if uppercase() => hex::encode_upper(bytes),
+            Style::Hex => hex::encode(bytes),
+            Style::Urn => {
+                let mut out = String::with_capacity(45);
+                out.push_str("urn:uuid:");
+                out.push_str(std::str::from_utf8(&encode_canonical(bytes)).expect("canonical form is ASCII"));
+                out
+            }
+            Style::Base64Url => URL_SAFE_NO_PAD.encode(bytes),
+        }
+    }
+}
+
+/// Format existing IDs in parallel, without the GIL.
+///
+/// `ids` is a `UuidArray` or any iterable of IDs. `style` is `"canonical"`,
+/// `"hex"` (32 digits), `"urn"` (`urn:uuid:` prefix) or `"base64url"`
+/// (22 characters, as `UUID.to_url_component`).
+#[pyfunction]
+#[pyo3(signature = (ids, style="canonical"))]
+pub fn format_batch(py: Python<'_>, ids: &Bound<'_, PyAny>, style: &str) -> PyResult<Vec<String>> {
+    let style = Style::parse(style)?;
+    let ids = batch_bytes(ids)?;
+    Ok(py.allow_threads(|| ids.par_iter().map(|id| style.format(id)).collect()))
+}
+
+/// `s` split into groups of `size` characters joined by `sep`, counted from
+/// the start, like a license key: `group("ABCDEFGHIJ")` is `"ABCD-EFGH-IJ"`.
+/// Works for any token, such as a `nano_id` or a base32 UUID.
+#[pyfunction]
+#[pyo3(signature = (s, size=4, sep="-"))]
+pub fn group(s: &str, size: usize, sep: &str) -> PyResult<String> {
+    if size == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Group size must be at least 1"));
+    }
+    let mut out = String::with_capacity(s.len() + s.len() / size * sep.len());
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && i % size == 0 {
+            out.push_str(sep);
+        }
+        out.push(c);
+    }
+    Ok(out)
+}
+
+/// Undo `group`: `s` without any `sep` or whitespace, as typed or pasted
+/// back by a user.
+#[pyfunction]
+#[pyo3(signature = (s, sep="-"))]
+pub fn ungroup(s: &str, sep: &str) -> String {
+    let s = if sep.is_empty() { s.to_string() } else { s.replace(sep, "") };
+    s.chars().filter(|c| !c.is_whitespace()).collect()
+}
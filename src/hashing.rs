@@ -0,0 +1,99 @@
+//! The hash behind `UUID.__hash__`, also available in bulk.
+//!
+//! The algorithm is part of the public contract and will not change between
+//! releases: with `hi`/`lo` the big-endian 64-bit halves of the ID,
+//! `h = mix64(hi ^ mix64(lo))`, where `mix64` is the SplitMix64 finalizer.
+//! `h` is read as a signed 64-bit integer and -1 becomes -2.
+//!
+//! `set_hash_mode("stdlib")` switches to `hash(uuid.int)`, the hash of stdlib
+//! `uuid.UUID`, so the two types can be mixed as dict keys.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::PyType;
+use rayon::prelude::*;
+
+use crate::array::batch_bytes;
+
+/// SplitMix64 finalizer (Steele, Lea & Flood), a bijective 64-bit mixer.
+pub(crate) fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Stable 64-bit hash of the 128 bits, independent of process and Rust version.
+pub(crate) fn fold128(bytes: &[u8; 16]) -> u64 {
+    let value = u128::from_be_bytes(*bytes);
+    mix64((value >> 64) as u64 ^ mix64(value as u64))
+}
+
+static STDLIB_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Modulus of CPython's numeric hash on 64-bit platforms (`sys.hash_info.modulus`).
+const PY_HASH_MODULUS: u128 = (1 << 61) - 1;
+
+/// `hash(uuid)` as Python sees it: `fold128` reinterpreted as a signed
+/// `Py_hash_t`, with -1 (reserved for errors) mapped to -2 as CPython does,
+/// or CPython's hash of the 128-bit integer in stdlib mode.
+pub(crate) fn uuid_hash(bytes: &[u8; 16]) -> i64 {
+    if STDLIB_MODE.load(Ordering::Relaxed) {
+        // Non-negative ints hash to their value modulo 2**61 - 1, never -1.
+        return (u128::from_be_bytes(*bytes) % PY_HASH_MODULUS) as i64;
+    }
+    match fold128(bytes) as i64 {
+        -1 => -2,
+        h => h,
+    }
+}
+
+/// The value of a stdlib `uuid.UUID`, when in stdlib mode. Equality with
+/// stdlib UUIDs is limited to that mode so equal objects always hash equal.
+pub(crate) fn stdlib_uuid_bytes(other: &Bound<'_, PyAny>) -> PyResult<Option<[u8; 16]>> {
+    static STDLIB_UUID: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    if !STDLIB_MODE.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+    let cls = STDLIB_UUID.import(other.py(), "uuid", "UUID")?;
+    if !other.is_instance(cls)? {
+        return Ok(None);
+    }
+    let value: u128 = other.getattr("int")?.extract()?;
+    Ok(Some(value.to_be_bytes()))
+}
+
+/// Select the `UUID.__hash__` algorithm: `"stable"` (the default, documented
+/// above) or `"stdlib"`, which matches `hash(uuid.UUID)` and makes
+/// `rustid.UUID` compare equal to stdlib UUIDs with the same value.
+///
+/// Switch modes before hashing any IDs: objects already stored in sets or
+/// dicts keep the hash they were inserted with.
+#[pyfunction]
+pub fn set_hash_mode(mode: &str) -> PyResult<()> {
+    let stdlib = match mode {
+        "stable" => false,
+        "stdlib" => true,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported hash mode: {}", mode))),
+    };
+    STDLIB_MODE.store(stdlib, Ordering::Relaxed);
+    Ok(())
+}
+
+#[pyfunction]
+pub fn get_hash_mode() -> &'static str {
+    if STDLIB_MODE.load(Ordering::Relaxed) {
+        "stdlib"
+    } else {
+        "stable"
+    }
+}
+
+/// `hash(id)` for every ID, computed in parallel; equal to the built-in
+/// `hash()` of the corresponding `rustid.UUID`.
+#[pyfunction]
+pub fn hash_batch(py: Python<'_>, ids: &Bound<'_, PyAny>) -> PyResult<Vec<i64>> {
+    let ids = batch_bytes(ids)?;
+    Ok(py.allow_threads(|| ids.par_iter().map(uuid_hash).collect()))
+}
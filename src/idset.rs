@@ -0,0 +1,132 @@
+//! Compressed sets of 64-bit IDs for segment and audience membership math.
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use roaring::RoaringTreemap;
+
+use crate::hashing::fold128;
+
+/// The 64-bit key of an ID: ints (snowflake-style IDs) are used as-is,
+/// anything `uuid_bytes` accepts is folded with the stable UUID hash.
+fn key(id: &Bound<'_, PyAny>) -> PyResult<u64> {
+    if let Ok(value) = id.extract::<u64>() {
+        return Ok(value);
+    }
+    Ok(fold128(&crate::uuid_bytes(id)?))
+}
+
+/// Set of 64-bit IDs backed by a roaring bitmap.
+///
+/// Exact for 64-bit schemes such as snowflake or `CompactIdGenerator` IDs.
+/// UUIDs are folded to 64 bits first, so membership of UUIDs may report
+/// rare false positives (about `n / 2**64` per lookup) and iteration yields
+/// the folded keys, not the UUIDs.
+#[pyclass]
+#[derive(Clone, Default)]
+pub struct IdSet {
+    bitmap: RoaringTreemap,
+}
+
+#[pymethods]
+impl IdSet {
+    #[new]
+    #[pyo3(signature = (ids=None))]
+    fn new(ids: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut set = IdSet::default();
+        if let Some(ids) = ids {
+            set.update(ids)?;
+        }
+        Ok(set)
+    }
+
+    fn add(&mut self, id: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.bitmap.insert(key(id)?);
+        Ok(())
+    }
+
+    fn discard(&mut self, id: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.bitmap.remove(key(id)?);
+        Ok(())
+    }
+
+    fn update(&mut self, ids: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut keys = ids.try_iter()?.map(|id| key(&id?)).collect::<PyResult<Vec<u64>>>()?;
+        keys.sort_unstable();
+        self.bitmap.extend(keys);
+        Ok(())
+    }
+
+    fn __contains__(&self, id: &Bound<'_, PyAny>) -> bool {
+        key(id).is_ok_and(|k| self.bitmap.contains(k))
+    }
+
+    fn __len__(&self) -> usize {
+        self.bitmap.len() as usize
+    }
+
+    fn __iter__(&self) -> IdSetIter {
+        IdSetIter { keys: self.bitmap.iter().collect::<Vec<u64>>().into_iter() }
+    }
+
+    fn __eq__(&self, other: &IdSet) -> bool {
+        self.bitmap == other.bitmap
+    }
+
+    fn __repr__(&self) -> String {
+        format!("IdSet(len={})", self.bitmap.len())
+    }
+
+    fn union(&self, other: &IdSet) -> IdSet {
+        IdSet { bitmap: &self.bitmap | &other.bitmap }
+    }
+
+    fn intersection(&self, other: &IdSet) -> IdSet {
+        IdSet { bitmap: &self.bitmap & &other.bitmap }
+    }
+
+    fn difference(&self, other: &IdSet) -> IdSet {
+        IdSet { bitmap: &self.bitmap - &other.bitmap }
+    }
+
+    fn __or__(&self, other: &IdSet) -> IdSet {
+        self.union(other)
+    }
+
+    fn __and__(&self, other: &IdSet) -> IdSet {
+        self.intersection(other)
+    }
+
+    fn __sub__(&self, other: &IdSet) -> IdSet {
+        self.difference(other)
+    }
+
+    /// Portable roaring serialization, readable by other roaring implementations.
+    fn serialize<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let mut out = Vec::with_capacity(self.bitmap.serialized_size());
+        self.bitmap.serialize_into(&mut out)?;
+        Ok(PyBytes::new(py, &out))
+    }
+
+    #[staticmethod]
+    fn deserialize(data: &[u8]) -> PyResult<IdSet> {
+        let bitmap = RoaringTreemap::deserialize_from(data)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid IdSet data: {}", e)))?;
+        Ok(IdSet { bitmap })
+    }
+}
+
+#[pyclass]
+pub struct IdSetIter {
+    keys: std::vec::IntoIter<u64>,
+}
+
+#[pymethods]
+impl IdSetIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<u64> {
+        self.keys.next()
+    }
+}
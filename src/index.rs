@@ -0,0 +1,131 @@
+//! Existence checks against an on-disk index: a file of packed 16-byte IDs
+//! in ascending byte order, written by `write_index`.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Deref;
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+/// Read-only view of a whole file, memory-mapped on Unix so lookups touch
+/// only the pages the binary search visits.
+struct Mapped {
+    #[cfg(unix)]
+    ptr: *mut libc::c_void,
+    #[cfg(unix)]
+    len: usize,
+    #[cfg(not(unix))]
+    data: Vec<u8>,
+}
+
+// The mapping is private and read-only, so sharing it across threads is safe.
+unsafe impl Send for Mapped {}
+unsafe impl Sync for Mapped {}
+
+impl Mapped {
+    #[cfg(unix)]
+    fn open(file: &File) -> std::io::Result<Self> {
+        use std::os::fd::AsRawFd;
+        let len = file.metadata()?.len() as usize;
+        if len == 0 {
+            // mmap rejects empty mappings.
+            return Ok(Mapped { ptr: std::ptr::null_mut(), len });
+        }
+        let ptr = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0)
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Mapped { ptr, len })
+    }
+
+    #[cfg(not(unix))]
+    fn open(mut file: &File) -> std::io::Result<Self> {
+        use std::io::Read;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        Ok(Mapped { data })
+    }
+}
+
+impl Deref for Mapped {
+    type Target = [u8];
+
+    #[cfg(unix)]
+    fn deref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+
+    #[cfg(not(unix))]
+    fn deref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mapped {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe { libc::munmap(self.ptr, self.len) };
+        }
+    }
+}
+
+fn contains(index: &[u8], id: &[u8; 16]) -> bool {
+    let records = index.len() / 16;
+    let record = |i: usize| &index[i * 16..i * 16 + 16];
+    let (mut lo, mut hi) = (0, records);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match record(mid).cmp(id.as_slice()) {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return true,
+        }
+    }
+    false
+}
+
+/// Write `ids` as an index file for `contains_batch`: sorted, without
+/// duplicates, 16 bytes per ID. Returns the number of IDs written.
+#[pyfunction]
+pub fn write_index(py: Python<'_>, path: PathBuf, ids: &Bound<'_, PyAny>) -> PyResult<usize> {
+    let mut ids = crate::array::batch_bytes(ids)?;
+    let file = File::create(path)?;
+    py.allow_threads(|| {
+        ids.par_sort_unstable();
+        ids.dedup();
+        let mut out = BufWriter::new(file);
+        for id in &ids {
+            out.write_all(id)?;
+        }
+        out.flush()?;
+        Ok(ids.len())
+    })
+}
+
+/// For each of `ids`, whether it is in the sorted index file at `index_path`.
+///
+/// The file is memory-mapped and each ID is binary-searched in parallel, so
+/// importers can skip already-known IDs without a database round-trip. Build
+/// the file with `write_index`, the only supported producer: the order is not
+/// re-checked on open, so an unsorted file gives wrong answers rather than an
+/// error.
+#[pyfunction]
+pub fn contains_batch(py: Python<'_>, index_path: PathBuf, ids: &Bound<'_, PyAny>) -> PyResult<Vec<bool>> {
+    let ids = crate::array::batch_bytes(ids)?;
+    let index = Mapped::open(&File::open(&index_path)?)?;
+    if index.len() % 16 != 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Index size {} is not a multiple of 16 bytes",
+            index.len()
+        )));
+    }
+    Ok(py.allow_threads(|| ids.par_iter().map(|id| contains(&index, id)).collect()))
+}
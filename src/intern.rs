@@ -0,0 +1,73 @@
+//! Opt-in interning of canonical strings produced by `str(uuid)`.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::encode_canonical;
+
+const DEFAULT_CAPACITY: usize = 65536;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CACHE: Mutex<Option<StrCache>> = Mutex::new(None);
+
+struct StrCache {
+    capacity: usize,
+    strings: HashMap<[u8; 16], Py<PyString>>,
+}
+
+fn new_string<'py>(py: Python<'py>, bytes: &[u8; 16]) -> Bound<'py, PyString> {
+    let out = encode_canonical(bytes);
+    PyString::new(py, std::str::from_utf8(&out).expect("canonical form is ASCII"))
+}
+
+/// Canonical string for `bytes`, shared with earlier calls while interning is on.
+pub(crate) fn canonical_pystring<'py>(py: Python<'py>, bytes: &[u8; 16]) -> Bound<'py, PyString> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return new_string(py, bytes);
+    }
+    let lock = || CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(s) = lock().as_ref().and_then(|cache| cache.strings.get(bytes).map(|s| s.clone_ref(py))) {
+        return s.into_bound(py);
+    }
+    // Allocate outside the lock: allocation may run arbitrary finalizers.
+    let s = new_string(py, bytes);
+    if let Some(cache) = lock().as_mut() {
+        // Full caches start over rather than tracking recency.
+        if cache.strings.len() >= cache.capacity {
+            cache.strings.clear();
+        }
+        cache.strings.insert(*bytes, s.clone().unbind());
+    }
+    s
+}
+
+/// Forget interned strings, e.g. after the output case changes.
+pub(crate) fn clear() {
+    let strings = CACHE.lock().unwrap_or_else(|e| e.into_inner()).as_mut().map(|cache| std::mem::take(&mut cache.strings));
+    drop(strings);
+}
+
+/// Turn interning of `str(uuid)` results on or off.
+///
+/// While on, formatting an ID that was formatted recently returns the same
+/// `str` object instead of allocating a new one, which helps code that
+/// stringifies the same IDs many times. At most `capacity` strings are
+/// kept; turning interning off releases them.
+#[pyfunction]
+#[pyo3(signature = (enabled=true, capacity=DEFAULT_CAPACITY))]
+pub fn intern_strings(enabled: bool, capacity: usize) -> PyResult<()> {
+    if enabled && capacity == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("capacity must be at least 1"));
+    }
+    let previous = {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        ENABLED.store(enabled, Ordering::Relaxed);
+        std::mem::replace(&mut *cache, enabled.then(|| StrCache { capacity, strings: HashMap::new() }))
+    };
+    drop(previous);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! Sortable string row keys for wide-column and document stores.
+
+use pyo3::prelude::*;
+
+use crate::alphabets::CROCKFORD32;
+use crate::audit;
+use crate::encoding::{crockford_digit, decode_u128, encode_u128};
+use crate::monotonic::next_shared;
+use crate::timestamp::reverse_v7;
+use crate::{canonical_string, UUID};
+
+/// Crockford base32 digits in an encoded 128-bit value.
+const KEY_LEN: usize = 26;
+
+const SALT_SEPARATOR: char = ':';
+
+/// 64-bit FNV-1a; stable across processes and platforms.
+pub(crate) fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |h, &b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Time-ordered key: `prefix` followed by a monotonic v7 in 26 Crockford
+/// base32 characters, so keys with the same prefix sort by creation time.
+/// With `reverse=True` they sort newest-first instead.
+#[pyfunction]
+#[pyo3(signature = (prefix=None, reverse=false))]
+pub fn sortable_key(py: Python<'_>, prefix: Option<&str>, reverse: bool) -> PyResult<String> {
+    let mut bytes = next_shared();
+    if reverse {
+        reverse_v7(&mut bytes);
+    }
+    let prefix = prefix.unwrap_or("");
+    let mut key = String::with_capacity(prefix.len() + KEY_LEN);
+    key.push_str(prefix);
+    key.push_str(&encode_u128(u128::from_be_bytes(bytes), CROCKFORD32.as_bytes()));
+    audit::record(py, "sortable_key", [&key])?;
+    Ok(key)
+}
+
+/// Split a key from `sortable_key` into `(prefix, id, timestamp_ms)`.
+/// Pass the same `reverse` flag the key was generated with.
+#[pyfunction]
+#[pyo3(signature = (key, reverse=false))]
+pub fn parse_sortable_key(key: &str, reverse: bool) -> PyResult<(String, UUID, u64)> {
+    let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid sortable key: {:?}", key));
+    let split = key.len().checked_sub(KEY_LEN).filter(|&i| key.is_char_boundary(i)).ok_or_else(invalid)?;
+    let (prefix, encoded) = key.split_at(split);
+    let mut bytes = decode_u128(encoded, 32, crockford_digit, "sortable key")?.to_be_bytes();
+    // Reversed keys hold a v8: `reverse_v7` inverts the version nibble too.
+    if bytes[6] >> 4 != if reverse { 8 } else { 7 } {
+        return Err(invalid());
+    }
+    if reverse {
+        reverse_v7(&mut bytes);
+    }
+    let timestamp_ms = u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]);
+    Ok((prefix.to_string(), UUID { bytes }, timestamp_ms))
+}
+
+/// Prefix `id` with a hash-derived bucket, e.g. `"07:<id>"`, to spread
+/// time-ordered writes over `buckets` partitions. The bucket depends only on
+/// the ID, and is zero-padded so keys within a bucket keep the ID's order.
+///
+/// `id` may be any string key (such as a `sortable_key`) or a UUID, which is
+/// used in canonical form.
+#[pyfunction]
+#[pyo3(signature = (id, buckets=16))]
+pub fn salted_key(id: &Bound<'_, PyAny>, buckets: u32) -> PyResult<String> {
+    if buckets == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("buckets must be at least 1"));
+    }
+    let id = match id.extract::<String>() {
+        Ok(s) => s,
+        Err(_) => canonical_string(&crate::uuid_bytes(id)?),
+    };
+    let bucket = fnv1a(id.as_bytes()) % buckets as u64;
+    let width = (buckets - 1).to_string().len();
+    Ok(format!("{:0width$}{}{}", bucket, SALT_SEPARATOR, id, width = width))
+}
+
+/// Recover the original ID string from a `salted_key`.
+///
+/// When `buckets` is given, the prefix must also be the bucket `salted_key`
+/// would have produced for that ID with the same `buckets`, padding included.
+#[pyfunction]
+#[pyo3(signature = (key, buckets=None))]
+pub fn unsalt_key(key: &str, buckets: Option<u32>) -> PyResult<String> {
+    let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid salted key: {:?}", key));
+    let (bucket, id) = match key.split_once(SALT_SEPARATOR) {
+        Some((bucket, id)) if !bucket.is_empty() && bucket.bytes().all(|c| c.is_ascii_digit()) => (bucket, id),
+        _ => return Err(invalid()),
+    };
+    if let Some(buckets) = buckets {
+        if buckets == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("buckets must be at least 1"));
+        }
+        let width = (buckets - 1).to_string().len();
+        if bucket.len() != width || bucket.parse::<u64>().ok() != Some(fnv1a(id.as_bytes()) % buckets as u64) {
+            return Err(invalid());
+        }
+    }
+    Ok(id.to_string())
+}
@@ -1,45 +1,222 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyDateTime, PyString, PyTuple, PyType, PyTzInfo};
 use base64::{engine::general_purpose::{URL_SAFE_NO_PAD, STANDARD}, Engine as _};
 use rayon::prelude::*;
 
-#[pyclass]
+mod alphabets;
+mod array;
+mod arrow;
+mod audit;
+mod buffer;
+mod checkdigit;
+mod codec;
+mod codename;
+mod coupon;
+mod columns;
+mod compare;
+mod context;
+mod cuid;
+mod derive;
+mod encoding;
+mod expiry;
+mod format;
+mod hashing;
+mod idset;
+mod index;
+mod intern;
+mod keys;
+mod marks;
+mod migrate;
+mod monotonic;
+mod packed;
+mod parse;
+mod pseudonymize;
+mod reader;
+mod region;
+mod registry;
+mod selfcheck;
+mod sequence;
+mod shard;
+mod stream;
+mod testdata;
+mod timestamp;
+mod trace;
+mod typeid;
+mod ulid;
+mod vectors;
+mod writer;
+
+/// Replace repeated values in `ids` with fresh ones from `generate` until all
+/// are distinct, for batches generated with `unique=True`. First occurrences
+/// keep their place.
+pub(crate) fn dedupe<T: Eq + Hash + Clone>(ids: &mut [T], generate: impl Fn() -> T) {
+    let mut seen = HashSet::with_capacity(ids.len());
+    for id in ids.iter_mut() {
+        while !seen.insert(id.clone()) {
+            *id = generate();
+        }
+    }
+}
+
+/// Canonical hyphenated form of `bytes` as ASCII, in the case chosen with
+/// `set_uppercase`.
+pub(crate) fn encode_canonical(bytes: &[u8; 16]) -> [u8; 36] {
+    let digits = if format::uppercase() { alphabets::HEX_UPPER } else { alphabets::HEX_LOWER }.as_bytes();
+    let mut out = [b'-'; 36];
+    let mut pos = 0;
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            pos += 1;
+        }
+        out[pos] = digits[(byte >> 4) as usize];
+        out[pos + 1] = digits[(byte & 0x0f) as usize];
+        pos += 2;
+    }
+    out
+}
+
+pub(crate) fn canonical_string(bytes: &[u8; 16]) -> String {
+    String::from_utf8(encode_canonical(bytes).to_vec()).expect("canonical form is ASCII")
+}
+
+/// Parse 32 hex digits, ignoring dashes.
+pub(crate) fn parse_hex(hex_str: &str) -> PyResult<[u8; 16]> {
+    let clean = hex_str.replace("-", "");
+    if clean.len() != 32 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid hex length"));
+    }
+    let mut bytes = [0u8; 16];
+    hex::decode_to_slice(&clean, &mut bytes)
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid hex"))?;
+    Ok(bytes)
+}
+
+/// Raw bytes of a `UUID`, a hex/canonical string, 16 raw bytes, or any object
+/// exposing a 16-byte `.bytes` attribute (such as stdlib `uuid.UUID`).
+pub(crate) fn uuid_bytes(obj: &Bound<'_, PyAny>) -> PyResult<[u8; 16]> {
+    if let Ok(id) = obj.downcast::<UUID>() {
+        return Ok(id.borrow().bytes);
+    }
+    if let Ok(s) = obj.extract::<&str>() {
+        return parse_hex(s);
+    }
+    if let Ok(raw) = obj.downcast::<PyBytes>() {
+        return raw.as_bytes().try_into()
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid bytes length"));
+    }
+    match obj.getattr("bytes") {
+        Ok(raw) => raw.extract::<[u8; 16]>()
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid bytes length")),
+        Err(_) => Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("Expected a UUID, str, or 16 bytes")),
+    }
+}
+
+/// Immutable, so instances carry no borrow flag. A pyclass freelist was
+/// measured and made batch construction slower than CPython's own small-object
+/// allocator, so none is used; batch cost is dominated by random generation.
+/// The module is set to the package so that pickle can find the class.
+#[pyclass(frozen, module = "rustid")]
 #[derive(Clone, Copy)]
 pub struct UUID {
     bytes: [u8; 16],
 }
 
+impl UUID {
+    /// Rich comparison against anything `compare::comparable_bytes` accepts,
+    /// or `NotImplemented`.
+    fn compare(&self, other: &Bound<'_, PyAny>, op: fn(std::cmp::Ordering) -> bool) -> PyResult<PyObject> {
+        let py = other.py();
+        Ok(match compare::comparable_bytes(other)? {
+            Some(bytes) => op(self.bytes.cmp(&bytes)).into_pyobject(py)?.to_owned().into_any().unbind(),
+            None => py.NotImplemented(),
+        })
+    }
+}
+
+// pyo3 methods cannot take `self` by value, so `to_*` conversions borrow.
+#[allow(clippy::wrong_self_convention)]
 #[pymethods]
 impl UUID {
+    /// Same arguments as stdlib `uuid.UUID`, plus `urn="urn:uuid:..."`.
+    /// Exactly one of `hex`, `bytes`, `bytes_le`, `fields`, `int` or `urn`
+    /// must be given; `version` then overwrites the version and variant bits.
     #[new]
-    fn new(hex: Option<&str>, bytes: Option<Bound<'_, PyBytes>>) -> PyResult<Self> {
-        if let Some(hex_str) = hex {
-            let clean = hex_str.replace("-", "");
-            if clean.len() != 32 {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid hex length"));
-            }
-            let mut bytes = [0u8; 16];
-            for i in 0..16 {
-                bytes[i] = u8::from_str_radix(&clean[i*2..i*2+2], 16)
-                    .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid hex"))?;
-            }
-            Ok(UUID { bytes })
+    #[pyo3(signature = (hex=None, bytes=None, bytes_le=None, fields=None, int=None, version=None, *, urn=None))]
+    fn new(
+        hex: Option<&str>,
+        bytes: Option<Bound<'_, PyBytes>>,
+        bytes_le: Option<Bound<'_, PyBytes>>,
+        fields: Option<(u32, u16, u16, u8, u8, u64)>,
+        int: Option<&Bound<'_, PyAny>>,
+        version: Option<u8>,
+        urn: Option<&str>,
+    ) -> PyResult<Self> {
+        let given = [hex.is_some(), bytes.is_some(), bytes_le.is_some(), fields.is_some(), int.is_some(), urn.is_some()];
+        if given.iter().filter(|&&g| g).count() != 1 {
+            return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+                "one of the hex, bytes, bytes_le, fields, int, or urn arguments must be given",
+            ));
+        }
+        let sixteen = |b: &Bound<'_, PyBytes>, name: &str| -> PyResult<[u8; 16]> {
+            b.as_bytes()
+                .try_into()
+                .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("{} is not a 16-char string", name)))
+        };
+        let mut bytes = if let Some(hex_str) = hex {
+            let bare = hex_str.replace("urn:", "").replace("uuid:", "");
+            parse_hex(bare.trim_matches(['{', '}']))?
         } else if let Some(py_bytes) = bytes {
-            let bytes_slice = py_bytes.as_bytes();
-            if bytes_slice.len() != 16 {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid bytes length"));
+            sixteen(&py_bytes, "bytes")?
+        } else if let Some(py_bytes) = bytes_le {
+            let mut bytes = sixteen(&py_bytes, "bytes_le")?;
+            bytes[0..4].reverse();
+            bytes[4..6].reverse();
+            bytes[6..8].reverse();
+            bytes
+        } else if let Some((time_low, time_mid, time_hi_version, clock_seq_hi_variant, clock_seq_low, node)) = fields {
+            if node >> 48 != 0 {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("field 6 out of range (need a 48-bit value)"));
             }
             let mut bytes = [0u8; 16];
-            bytes.copy_from_slice(bytes_slice);
-            Ok(UUID { bytes })
+            bytes[0..4].copy_from_slice(&time_low.to_be_bytes());
+            bytes[4..6].copy_from_slice(&time_mid.to_be_bytes());
+            bytes[6..8].copy_from_slice(&time_hi_version.to_be_bytes());
+            bytes[8] = clock_seq_hi_variant;
+            bytes[9] = clock_seq_low;
+            bytes[10..].copy_from_slice(&node.to_be_bytes()[2..]);
+            bytes
+        } else if let Some(int) = int {
+            let value: u128 = int
+                .extract()
+                .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("int is out of range (need a 128-bit value)"))?;
+            value.to_be_bytes()
         } else {
-            Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Either hex or bytes required"))
+            let urn = urn.expect("exactly one argument is given");
+            let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UUID URN: {:?}", urn));
+            let rest = urn.get(..9).filter(|p| p.eq_ignore_ascii_case("urn:uuid:")).map(|_| &urn[9..]).ok_or_else(invalid)?;
+            parse::parse_lenient(rest.as_bytes()).map_err(|_| invalid())?
+        };
+        if let Some(version) = version {
+            if !(1..=8).contains(&version) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("illegal version number"));
+            }
+            bytes[6] = (bytes[6] & 0x0f) | version << 4;
+            bytes[8] = (bytes[8] & 0x3f) | 0x80;
         }
+        Ok(UUID { bytes })
     }
 
     #[getter]
-    fn hex(&self) -> String {
-        hex::encode(self.bytes)
+    fn hex<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        let mut out = [0u8; 32];
+        hex::encode_to_slice(self.bytes, &mut out).expect("32 hex digits fit the buffer");
+        if format::uppercase() {
+            out.make_ascii_uppercase();
+        }
+        PyString::new(py, std::str::from_utf8(&out).expect("hex is ASCII"))
     }
 
     #[getter]
@@ -63,129 +240,1036 @@ impl UUID {
         }
     }
 
-    fn __str__(&self) -> String {
-        format!("{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
-            self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3],
-            self.bytes[4], self.bytes[5], self.bytes[6], self.bytes[7],
-            self.bytes[8], self.bytes[9], self.bytes[10], self.bytes[11],
-            self.bytes[12], self.bytes[13], self.bytes[14], self.bytes[15])
+    fn __str__<'py>(&self, py: Python<'py>) -> Bound<'py, PyString> {
+        intern::canonical_pystring(py, &self.bytes)
     }
 
     fn __repr__(&self) -> String {
-        format!("UUID('{}')", self.__str__())
+        let out = encode_canonical(&self.bytes);
+        format!("UUID('{}')", std::str::from_utf8(&out).expect("canonical form is ASCII"))
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.compare(other, |order| order.is_eq())
+    }
+
+    // Ordering is by bytes, i.e. by 128-bit value, as for stdlib `uuid.UUID`.
+    fn __lt__(&self, other: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.compare(other, |order| order.is_lt())
+    }
+
+    fn __le__(&self, other: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.compare(other, |order| order.is_le())
+    }
+
+    fn __gt__(&self, other: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.compare(other, |order| order.is_gt())
+    }
+
+    fn __ge__(&self, other: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        self.compare(other, |order| order.is_ge())
+    }
+
+    fn __hash__(&self) -> isize {
+        hashing::uuid_hash(&self.bytes) as isize
+    }
+
+    // Instances are immutable, so unpickling rebuilds them through the
+    // constructor instead of `__setstate__`, and copies can share the object.
+    fn __reduce__<'py>(slf: &Bound<'py, Self>) -> PyResult<(Bound<'py, PyType>, Bound<'py, PyTuple>)> {
+        let py = slf.py();
+        let args = PyTuple::new(py, [py.None().into_bound(py), PyBytes::new(py, &slf.get().bytes).into_any()])?;
+        Ok((slf.get_type(), args))
     }
 
-    fn __eq__(&self, other: &UUID) -> bool {
-        self.bytes == other.bytes
+    fn __getstate__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.bytes)
+    }
+
+    fn __copy__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __deepcopy__(slf: Py<Self>, _memo: &Bound<'_, PyAny>) -> Py<Self> {
+        slf
     }
 
-    fn __hash__(&self) -> u64 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-        let mut hasher = DefaultHasher::new();
-        self.bytes.hash(&mut hasher);
-        hasher.finish()
+    /// 16 characters from the first 12 bytes, or with `full=True` all 16
+    /// bytes in 22 characters, which `from_short_id` can decode. With
+    /// `sortable=True` the symbols come from `alphabets.SORTABLE64`, so the
+    /// text sorts like the bytes.
+    #[pyo3(signature = (full=false, sortable=false))]
+    fn short_id(&self, full: bool, sortable: bool) -> String {
+        short_id_string(&self.bytes, full, sortable)
     }
 
-    fn short_id(&self) -> String {
-        URL_SAFE_NO_PAD.encode(&self.bytes[0..12])  // Use 12 bytes instead of 9
+    /// Inverse of `short_id(full=True)`, with the same `sortable`.
+    #[staticmethod]
+    #[pyo3(signature = (s, sortable=false))]
+    fn from_short_id(s: &str, sortable: bool) -> PyResult<UUID> {
+        if s.len() == 16 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "16-character short IDs keep only 12 of 16 bytes and cannot be decoded; use short_id(full=True)",
+            ));
+        }
+        if !sortable {
+            return UUID::from_url_component(s).map_err(|_| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid short ID: expected 22 base64url characters")
+            });
+        }
+        SORTABLE_NO_PAD
+            .decode(s)
+            .ok()
+            .and_then(|decoded| <[u8; 16]>::try_from(decoded).ok())
+            .map(|bytes| UUID { bytes })
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid short ID: expected 22 sortable base64 characters")
+            })
     }
 
-    fn base64(&self) -> String {
-        STANDARD.encode(&self.bytes)
+    /// Deprecated alias of `to_base64`, kept for existing callers.
+    fn base64(&self, py: Python<'_>) -> PyResult<String> {
+        PyErr::warn(
+            py,
+            &py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+            c"UUID.base64() is deprecated; use UUID.to_base64()",
+            1,
+        )?;
+        Ok(self.to_base64())
+    }
+
+    /// 24 characters of padded standard base64.
+    fn to_base64(&self) -> String {
+        STANDARD.encode(self.bytes)
+    }
+
+    #[staticmethod]
+    fn from_base64(s: &str) -> PyResult<UUID> {
+        let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid base64 UUID: expected 24 characters");
+        if s.len() != 24 {
+            return Err(invalid());
+        }
+        let decoded = STANDARD.decode(s).map_err(|_| invalid())?;
+        Ok(UUID { bytes: decoded.try_into().map_err(|_| invalid())? })
+    }
+
+    /// 22 characters of unpadded URL-safe base64 (same as `to_url_component`).
+    fn to_base64url(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.bytes)
+    }
+
+    /// Inverse of `to_base64url`; also accepts the padded 24-character form.
+    #[staticmethod]
+    fn from_base64url(s: &str) -> PyResult<UUID> {
+        UUID::from_url_component(s.strip_suffix("==").unwrap_or(s))
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid base64url UUID: expected 22 characters"))
     }
 
     fn int(&self) -> u128 {
         u128::from_be_bytes(self.bytes)
     }
+
+    #[getter]
+    fn bytes_le<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let mut bytes = self.bytes;
+        bytes[0..4].reverse();
+        bytes[4..6].reverse();
+        bytes[6..8].reverse();
+        PyBytes::new(py, &bytes)
+    }
+
+    /// `(time_low, time_mid, time_hi_version, clock_seq_hi_variant,
+    /// clock_seq_low, node)`, as in stdlib `uuid.UUID.fields`.
+    #[getter]
+    fn fields(&self) -> (u32, u16, u16, u8, u8, u64) {
+        (self.time_low(), self.time_mid(), self.time_hi_version(), self.bytes[8], self.bytes[9], self.node())
+    }
+
+    #[getter]
+    fn time_low(&self) -> u32 {
+        u32::from_be_bytes([self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3]])
+    }
+
+    #[getter]
+    fn time_mid(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[4], self.bytes[5]])
+    }
+
+    #[getter]
+    fn time_hi_version(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[6], self.bytes[7]])
+    }
+
+    #[getter]
+    fn clock_seq_hi_variant(&self) -> u8 {
+        self.bytes[8]
+    }
+
+    #[getter]
+    fn clock_seq_low(&self) -> u8 {
+        self.bytes[9]
+    }
+
+    #[getter]
+    fn clock_seq(&self) -> u16 {
+        u16::from_be_bytes([self.bytes[8] & 0x3f, self.bytes[9]])
+    }
+
+    #[getter]
+    fn node(&self) -> u64 {
+        u64::from_be_bytes([0, 0, self.bytes[10], self.bytes[11], self.bytes[12], self.bytes[13], self.bytes[14], self.bytes[15]])
+    }
+
+    /// The 60-bit v1 timestamp field, computed as stdlib does for any version.
+    #[getter]
+    fn time(&self) -> u64 {
+        ((self.time_hi_version() & 0x0fff) as u64) << 48 | (self.time_mid() as u64) << 32 | self.time_low() as u64
+    }
+
+    /// Creation time in Unix seconds, for versions 1, 6 and 7.
+    #[getter]
+    fn timestamp(&self) -> PyResult<f64> {
+        timestamp::unix_seconds_of(&self.bytes).ok_or_else(|| timestamp::no_timestamp(&self.bytes))
+    }
+
+    /// Creation time in Unix milliseconds, for versions 1, 6 and 7.
+    #[getter]
+    fn timestamp_ms(&self) -> PyResult<u64> {
+        timestamp::unix_ms_of(&self.bytes).ok_or_else(|| timestamp::no_timestamp(&self.bytes))
+    }
+
+    /// Creation time as an aware UTC `datetime`, for versions 1, 6 and 7.
+    #[getter]
+    fn datetime<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDateTime>> {
+        let utc = PyTzInfo::utc(py)?;
+        PyDateTime::from_timestamp(py, self.timestamp()?, Some(&utc))
+    }
+
+    #[getter]
+    fn urn(&self) -> String {
+        format!("urn:uuid:{}", canonical_string(&self.bytes))
+    }
+
+    fn base36(&self) -> String {
+        encoding::encode_u128(u128::from_be_bytes(self.bytes), alphabets::BASE36.as_bytes())
+    }
+
+    #[staticmethod]
+    fn from_base36(s: &str) -> PyResult<UUID> {
+        let value = encoding::decode_u128(s, 36, encoding::base36_digit, "base36")?;
+        Ok(UUID { bytes: value.to_be_bytes() })
+    }
+
+    /// 26 uppercase Crockford base32 characters, which avoid the easily
+    /// confused `I`, `L`, `O` and `U`.
+    fn base32(&self) -> String {
+        encoding::encode_u128(u128::from_be_bytes(self.bytes), alphabets::CROCKFORD32.as_bytes())
+    }
+
+    /// Inverse of `base32`: case-insensitive, reading `I`/`L` as 1 and `O`
+    /// as 0, and ignoring hyphens, as Crockford's spec allows.
+    #[staticmethod]
+    fn from_base32(s: &str) -> PyResult<UUID> {
+        let s: String = s.chars().filter(|&c| c != '-').collect();
+        let value = encoding::decode_u128(&s, 32, encoding::crockford_digit, "base32")?;
+        Ok(UUID { bytes: value.to_be_bytes() })
+    }
+
+    /// 22 characters of the Bitcoin base58 alphabet, left-padded with `1`
+    /// (the zero digit) so that encodings sort like the UUIDs.
+    fn base58(&self) -> String {
+        encoding::encode_u128(u128::from_be_bytes(self.bytes), alphabets::BASE58.as_bytes())
+    }
+
+    /// Inverse of `base58`; also reads the unpadded output of Bitcoin-style
+    /// encoders such as `base58.b58encode(id.bytes)`.
+    #[staticmethod]
+    fn from_base58(s: &str) -> PyResult<UUID> {
+        let value = encoding::decode_u128(s, 58, encoding::base58_digit, "base58")?;
+        Ok(UUID { bytes: value.to_be_bytes() })
+    }
+
+    /// The 128 bits as an `ipaddress.IPv6Address`.
+    fn to_ipv6(&self) -> std::net::Ipv6Addr {
+        std::net::Ipv6Addr::from(self.bytes)
+    }
+
+    #[staticmethod]
+    fn from_ipv6(address: std::net::IpAddr) -> PyResult<UUID> {
+        match address {
+            std::net::IpAddr::V6(v6) => Ok(UUID { bytes: v6.octets() }),
+            std::net::IpAddr::V4(_) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Expected an IPv6 address")),
+        }
+    }
+
+    /// 25 lowercase alphanumerics (fixed-width base36): safe on
+    /// case-insensitive filesystems, never a reserved device name, sorts like the ID.
+    fn to_filename(&self) -> String {
+        self.base36()
+    }
+
+    #[staticmethod]
+    fn from_filename(s: &str) -> PyResult<UUID> {
+        if s.len() != 25 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Filename form must be 25 characters"));
+        }
+        UUID::from_base36(s)
+    }
+
+    /// 22 characters of unpadded base64url, using only RFC 3986 unreserved characters.
+    fn to_url_component(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.bytes)
+    }
+
+    #[staticmethod]
+    fn from_url_component(s: &str) -> PyResult<UUID> {
+        let invalid = || PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid URL component");
+        if s.len() != 22 {
+            return Err(invalid());
+        }
+        let decoded = URL_SAFE_NO_PAD.decode(s).map_err(|_| invalid())?;
+        Ok(UUID { bytes: decoded.try_into().map_err(|_| invalid())? })
+    }
+
+    /// Value for HTTP/gRPC metadata headers: always exactly 22 characters of
+    /// unpadded base64url, which are all valid `token68` header characters.
+    fn to_header(&self) -> String {
+        self.to_url_component()
+    }
+
+    /// Strict inverse of `to_header`: rejects any other length, padding,
+    /// whitespace, or non-canonical trailing bits.
+    #[staticmethod]
+    fn from_header(s: &str) -> PyResult<UUID> {
+        UUID::from_url_component(s)
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid header ID: expected 22 base64url characters"))
+    }
+}
+
+/// Per-process random node with the multicast bit set (RFC 9562, section 6.10),
+/// so time-based IDs never expose a hardware address.
+fn private_node() -> [u8; 6] {
+    static NODE: std::sync::OnceLock<[u8; 6]> = std::sync::OnceLock::new();
+    *NODE.get_or_init(|| {
+        let mut node = [0u8; 6];
+        node.copy_from_slice(&fastrand::u64(..).to_be_bytes()[2..]);
+        node[0] |= 0x01;
+        node
+    })
+}
+
+/// MAC address of the first network interface (by name) with a globally
+/// unique unicast address, read from `/sys/class/net`; `None` elsewhere or
+/// when there is no such interface.
+fn hardware_node() -> Option<[u8; 6]> {
+    static NODE: std::sync::OnceLock<Option<[u8; 6]>> = std::sync::OnceLock::new();
+    *NODE.get_or_init(|| {
+        let mut names: Vec<_> = std::fs::read_dir("/sys/class/net").ok()?.flatten().map(|e| e.path()).collect();
+        names.sort();
+        names.iter().find_map(|path| {
+            let text = std::fs::read_to_string(path.join("address")).ok()?;
+            let mut node = [0u8; 6];
+            let mut parts = text.trim().split(':');
+            for byte in &mut node {
+                *byte = u8::from_str_radix(parts.next()?, 16).ok()?;
+            }
+            // Skip loopback (all zero) and multicast or locally administered
+            // addresses (virtual interfaces), which are not globally unique.
+            (parts.next().is_none() && node != [0; 6] && node[0] & 0x03 == 0).then_some(node)
+        })
+    })
+}
+
+/// v1 UUID: Gregorian timestamp, clock sequence and node (RFC 9562, section 5.1).
+///
+/// The node defaults to the host's MAC address, or to a stable per-process
+/// random node with the multicast bit set when there is none or with
+/// `privacy=True`. The clock sequence starts random and is kept for the
+/// process; it advances whenever the clock goes backwards (or repeats a
+/// 100ns tick), so IDs stay unique across clock regressions. `node` (48 bits)
+/// and `clock_seq` (14 bits) override the generated fields; a fixed
+/// `clock_seq` is not advanced.
+#[pyfunction]
+#[pyo3(signature = (privacy=false, node=None, clock_seq=None))]
+fn uuid1(py: Python<'_>, privacy: bool, node: Option<u64>, clock_seq: Option<u16>) -> PyResult<UUID> {
+    let node = time_node(node, privacy)?;
+    let clock_seq = check_clock_seq(clock_seq)?;
+    let id = UUID { bytes: v1_bytes(&node, gregorian_now(clock_seq)) };
+    audit::record(py, "uuid1", [id])?;
+    Ok(id)
+}
+
+/// Node for a time-based ID: the explicit 48-bit `node` if given, else the
+/// private node with `privacy`, else the MAC address if there is one.
+fn time_node(node: Option<u64>, privacy: bool) -> PyResult<[u8; 6]> {
+    match node {
+        Some(node) if node >> 48 != 0 => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("node must fit in 48 bits")),
+        Some(node) => Ok(node.to_be_bytes()[2..].try_into().expect("6 bytes")),
+        None if privacy => Ok(private_node()),
+        None => Ok(hardware_node().unwrap_or_else(private_node)),
+    }
+}
+
+fn check_clock_seq(clock_seq: Option<u16>) -> PyResult<Option<u16>> {
+    match clock_seq {
+        Some(seq) if seq >> 14 != 0 => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("clock_seq must fit in 14 bits")),
+        _ => Ok(clock_seq),
+    }
+}
+
+/// Last Gregorian tick and clock sequence handed out by this process.
+static CLOCK: std::sync::Mutex<Option<(u64, u16)>> = std::sync::Mutex::new(None);
+
+/// Current Gregorian timestamp with an explicit `clock_seq`, or with the
+/// process-wide clock sequence, which advances when the clock does not
+/// (RFC 9562, section 6.1).
+fn gregorian_now(clock_seq: Option<u16>) -> uuid::Timestamp {
+    let (ticks, _) = uuid::Timestamp::now(uuid::timestamp::context::NoContext).to_gregorian();
+    let seq = clock_seq.unwrap_or_else(|| {
+        let mut clock = CLOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let seq = match *clock {
+            Some((last, seq)) if ticks <= last => (seq + 1) & 0x3fff,
+            Some((_, seq)) => seq,
+            None => fastrand::u16(..0x4000),
+        };
+        *clock = Some((ticks, seq));
+        seq
+    });
+    uuid::Timestamp::from_gregorian_time(ticks, seq)
+}
+
+fn v1_bytes(node: &[u8; 6], timestamp: uuid::Timestamp) -> [u8; 16] {
+    *uuid::Uuid::new_v1(timestamp, node).as_bytes()
 }
 
+fn v6_bytes(node: &[u8; 6], timestamp: uuid::Timestamp) -> [u8; 16] {
+    *uuid::Uuid::new_v6(timestamp, node).as_bytes()
+}
+
+/// v6 UUID: the v1 Gregorian timestamp reordered most-significant first, so
+/// IDs sort by creation time (RFC 9562, section 5.6).
+///
+/// `node` (48 bits) and `clock_seq` (14 bits) override the generated fields,
+/// e.g. to reproduce captured IDs in tests. A fixed `clock_seq` is not
+/// advanced, so IDs created within the same 100ns tick are then equal.
 #[pyfunction]
-fn uuid1() -> UUID {
-    let id = uuid::Uuid::now_v1(&[1, 2, 3, 4, 5, 6]);
-    UUID { bytes: *id.as_bytes() }
+#[pyo3(signature = (privacy=false, node=None, clock_seq=None))]
+fn uuid6(py: Python<'_>, privacy: bool, node: Option<u64>, clock_seq: Option<u16>) -> PyResult<UUID> {
+    let node = time_node(node, privacy)?;
+    let clock_seq = check_clock_seq(clock_seq)?;
+    let id = UUID { bytes: v6_bytes(&node, gregorian_now(clock_seq)) };
+    audit::record(py, "uuid6", [id])?;
+    Ok(id)
 }
 
 #[pyfunction]
-fn uuid4() -> UUID {
-    let id = uuid::Uuid::new_v4();
-    UUID { bytes: *id.as_bytes() }
+fn uuid4(py: Python<'_>) -> PyResult<UUID> {
+    let id = UUID { bytes: *uuid::Uuid::new_v4().as_bytes() };
+    audit::record(py, "uuid4", [id])?;
+    Ok(id)
 }
 
+/// v7 UUID; with `reverse=True` the timestamp bits are inverted so that
+/// newer IDs sort first (for Bigtable/HBase-style row keys). Reversed IDs
+/// are v8, since their timestamp field no longer holds the creation time.
 #[pyfunction]
-fn uuid7() -> UUID {
-    let id = uuid::Uuid::now_v7();
-    UUID { bytes: *id.as_bytes() }
+#[pyo3(signature = (reverse=false))]
+fn uuid7(py: Python<'_>, reverse: bool) -> PyResult<UUID> {
+    let mut bytes = *uuid::Uuid::now_v7().as_bytes();
+    if reverse {
+        timestamp::reverse_v7(&mut bytes);
+    }
+    audit::record(py, "uuid7", [UUID { bytes }])?;
+    Ok(UUID { bytes })
 }
 
+/// v7 UUID that is strictly greater than every v7 this process has issued
+/// through the shared counter, also across threads.
+///
+/// Uses a 12-bit counter in `rand_a` (RFC 9562, section 6.2, method 1) that
+/// increments within a millisecond and shares its state with `sortable_key`
+/// and `short_id(monotonic=True)`. If the counter runs out within one
+/// millisecond, `overflow="borrow"` (the default) continues in the next
+/// millisecond, `"wait"` sleeps until the clock gets there, and `"raise"`
+/// raises `OverflowError`.
 #[pyfunction]
-fn uuid4_batch(count: usize) -> Vec<UUID> {
-    (0..count)
-        .into_par_iter()
-        .map(|_| UUID { bytes: *uuid::Uuid::new_v4().as_bytes() })
-        .collect()
+#[pyo3(signature = (overflow="borrow"))]
+fn uuid7_monotonic(py: Python<'_>, overflow: &str) -> PyResult<UUID> {
+    let overflow = monotonic::Overflow::parse(overflow)?;
+    let bytes = py.allow_threads(|| monotonic::next_shared_with(overflow))?;
+    audit::record(py, "uuid7", [UUID { bytes }])?;
+    Ok(UUID { bytes })
 }
 
+/// `count` strictly increasing v7 UUIDs from the shared counter of
+/// `uuid7_monotonic`, with no other caller's IDs in between.
 #[pyfunction]
-fn uuid7_batch(count: usize) -> Vec<UUID> {
-    (0..count)
-        .into_par_iter()
-        .map(|_| UUID { bytes: *uuid::Uuid::now_v7().as_bytes() })
-        .collect()
+#[pyo3(signature = (count, overflow="borrow", returns="list"))]
+fn uuid7_monotonic_batch(py: Python<'_>, count: usize, overflow: &str, returns: &str) -> PyResult<PyObject> {
+    let overflow = monotonic::Overflow::parse(overflow)?;
+    let returns = array::Returns::parse(returns)?;
+    let ids = py.allow_threads(|| monotonic::next_shared_batch(count, overflow))?;
+    audit::record(py, "uuid7", ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
 }
 
+/// Name bytes as stdlib `uuid` takes them: `str` is UTF-8 encoded, `bytes` used as-is.
+fn name_bytes(name: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(s) = name.downcast::<PyString>() {
+        return Ok(s.to_str()?.as_bytes().to_vec());
+    }
+    name.extract::<Vec<u8>>()
+}
+
+/// Name-based v3 (MD5) UUID; same result as `uuid.uuid3(namespace, name)`.
 #[pyfunction]
-fn short_id() -> String {
-    let id = uuid::Uuid::now_v7();
-    URL_SAFE_NO_PAD.encode(&id.as_bytes()[0..12])  // Use 12 bytes for better uniqueness
+fn uuid3(py: Python<'_>, namespace: &Bound<'_, PyAny>, name: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let namespace = uuid::Uuid::from_bytes(uuid_bytes(namespace)?);
+    let id = UUID { bytes: *uuid::Uuid::new_v3(&namespace, &name_bytes(name)?).as_bytes() };
+    audit::record(py, "uuid3", [id])?;
+    Ok(id)
 }
 
+/// Name-based v5 (SHA-1) UUID; same result as `uuid.uuid5(namespace, name)`.
 #[pyfunction]
-fn short_id_batch(count: usize) -> Vec<String> {
-    (0..count)
-        .into_par_iter()
-        .map(|_| {
-            let id = uuid::Uuid::now_v7();
-            URL_SAFE_NO_PAD.encode(&id.as_bytes()[0..12])  // Use 12 bytes
-        })
-        .collect()
+fn uuid5(py: Python<'_>, namespace: &Bound<'_, PyAny>, name: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let namespace = uuid::Uuid::from_bytes(uuid_bytes(namespace)?);
+    let id = UUID { bytes: *uuid::Uuid::new_v5(&namespace, &name_bytes(name)?).as_bytes() };
+    audit::record(py, "uuid5", [id])?;
+    Ok(id)
 }
 
+/// v8 UUID carrying caller-defined data (RFC 9562, section 5.8).
+///
+/// `custom` is either 16 bytes, whose version and variant bits are
+/// overwritten, or an int payload below 2**122 that is spread over the
+/// `custom_a` (48 bits), `custom_b` (12 bits) and `custom_c` (62 bits) fields
+/// so that no payload bit is lost.
 #[pyfunction]
-#[pyo3(signature = (size=None))]
-fn nano_id(size: Option<usize>) -> String {
-    let alphabet = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_";
-    let size = size.unwrap_or(21);
-    let mut result = String::with_capacity(size);
-    
-    for _ in 0..size {
-        let idx = fastrand::usize(0..alphabet.len());
-        result.push(alphabet.chars().nth(idx).unwrap());
+fn uuid8(py: Python<'_>, custom: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let id = uuid8_from(custom)?;
+    audit::record(py, "uuid8", [id])?;
+    Ok(id)
+}
+
+fn uuid8_from(custom: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    if let Ok(payload) = custom.extract::<u128>() {
+        if payload >> 122 != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("uuid8 payload must be less than 2**122"));
+        }
+        let value = (payload >> 74) << 80 | 0x8 << 76 | (payload >> 62 & 0xfff) << 64 | 0b10 << 62 | payload & ((1 << 62) - 1);
+        return Ok(UUID { bytes: value.to_be_bytes() });
+    }
+    if custom.downcast::<pyo3::types::PyInt>().is_ok() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("uuid8 payload must be a non-negative int less than 2**122"));
+    }
+    let bytes: [u8; 16] = custom
+        .extract::<Vec<u8>>()?
+        .try_into()
+        .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("uuid8 takes exactly 16 bytes"))?;
+    Ok(UUID { bytes: *uuid::Uuid::new_v8(bytes).as_bytes() })
+}
+
+/// Batch functions take `returns=` to pick the output: `"list"` of `UUID`
+/// (the default), `"str"` for canonical strings, `"bytes"` for one packed
+/// buffer, `"array"` for a `UuidArray`, or `"iter"` for an iterator.
+///
+/// Random batch generators also take `unique=True`, which checks the batch
+/// for duplicates and regenerates any it finds, for compliance rules that
+/// demand a guarantee rather than a negligible collision probability.
+#[pyfunction]
+#[pyo3(signature = (count, returns="list", unique=false))]
+fn uuid4_batch(py: Python<'_>, count: usize, returns: &str, unique: bool) -> PyResult<PyObject> {
+    let returns = array::Returns::parse(returns)?;
+    let ids: Vec<[u8; 16]> = py.allow_threads(|| {
+        let generate = || *uuid::Uuid::new_v4().as_bytes();
+        let mut ids: Vec<[u8; 16]> = (0..count).into_par_iter().map(|_| generate()).collect();
+        if unique {
+            dedupe(&mut ids, generate);
+        }
+        ids
+    });
+    audit::record(py, "uuid4", ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
+}
+
+#[pyfunction]
+#[pyo3(signature = (count, reverse=false, returns="list", unique=false))]
+fn uuid7_batch(py: Python<'_>, count: usize, reverse: bool, returns: &str, unique: bool) -> PyResult<PyObject> {
+    let returns = array::Returns::parse(returns)?;
+    let ids: Vec<[u8; 16]> = py.allow_threads(|| {
+        let generate = || {
+            let mut bytes = *uuid::Uuid::now_v7().as_bytes();
+            if reverse {
+                timestamp::reverse_v7(&mut bytes);
+            }
+            bytes
+        };
+        let mut ids: Vec<[u8; 16]> = (0..count).into_par_iter().map(|_| generate()).collect();
+        if unique {
+            dedupe(&mut ids, generate);
+        }
+        ids
+    });
+    audit::record(py, "uuid7", ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
+}
+
+/// Bits of the sequence counter in a partitioned v7: all 12 of `rand_a`
+/// and the top 30 of `rand_b` (RFC 9562, section 6.2, method 1).
+const PARTITION_COUNTER_BITS: u32 = 42;
+
+/// v7 for position `index` of a partitioned batch starting at `base_ms`.
+fn partition_v7(base_ms: u64, index: u64) -> [u8; 16] {
+    let ms = base_ms.wrapping_add(index >> PARTITION_COUNTER_BITS) & 0xffff_ffff_ffff;
+    let counter = index & ((1 << PARTITION_COUNTER_BITS) - 1);
+    let value = (ms as u128) << 80
+        | 0x7 << 76
+        | ((counter >> 30) as u128) << 64
+        | 0b10 << 62
+        | ((counter & ((1 << 30) - 1)) as u128) << 32
+        | fastrand::u32(..) as u128;
+    value.to_be_bytes()
+}
+
+/// Worker `worker_index`'s share of a `total`-ID v7 batch split across
+/// `workers` processes, with no coordination beyond the arguments.
+///
+/// Position `i` of the batch gets the timestamp `timestamp` and a 42-bit
+/// counter of `i` in place of the leading random bits, and each worker owns
+/// one contiguous run of positions, so no two workers can produce the same
+/// ID and the concatenation of all shares in worker order is sorted. Pass
+/// every worker the same `timestamp` (Unix ms or `datetime`), chosen by the
+/// parent; with the default of the current time each worker's IDs are still
+/// unique, but shares are no longer ordered relative to each other.
+/// `start` skips that many IDs of the share, to resume an interrupted worker.
+#[pyfunction]
+#[pyo3(signature = (total, workers, worker_index, timestamp=None, start=0, returns="list"))]
+fn uuid7_batch_partition(
+    py: Python<'_>,
+    total: u64,
+    workers: u64,
+    worker_index: u64,
+    timestamp: Option<&Bound<'_, PyAny>>,
+    start: u64,
+    returns: &str,
+) -> PyResult<PyObject> {
+    let returns = array::Returns::parse(returns)?;
+    if worker_index >= workers {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "worker_index must be below workers ({}), got {}",
+            workers, worker_index
+        )));
+    }
+    let base_ms = match timestamp {
+        Some(timestamp) => timestamp::unix_ms(timestamp)?,
+        None => monotonic::now_ms(),
+    };
+    let bound = |w: u64| (total as u128 * w as u128 / workers as u128) as u64;
+    let (first, end) = (bound(worker_index), bound(worker_index + 1));
+    if start > end - first {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "start {} is past the end of this worker's {} IDs",
+            start,
+            end - first
+        )));
+    }
+    let ids: Vec<[u8; 16]> =
+        py.allow_threads(|| (first + start..end).into_par_iter().map(|i| partition_v7(base_ms, i)).collect());
+    audit::record(py, "uuid7", ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
+}
+
+#[pyfunction]
+#[pyo3(signature = (count, privacy=false, returns="list", node=None, clock_seq=None))]
+fn uuid6_batch(
+    py: Python<'_>,
+    count: usize,
+    privacy: bool,
+    returns: &str,
+    node: Option<u64>,
+    clock_seq: Option<u16>,
+) -> PyResult<PyObject> {
+    let returns = array::Returns::parse(returns)?;
+    let node = time_node(node, privacy)?;
+    let clock_seq = check_clock_seq(clock_seq)?;
+    let ids: Vec<[u8; 16]> =
+        py.allow_threads(|| (0..count).into_par_iter().map(|_| v6_bytes(&node, gregorian_now(clock_seq))).collect());
+    audit::record(py, "uuid6", ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
+}
+
+/// Name-based UUIDs from `make` for each of `names` (str or bytes, as
+/// `name_bytes` reads them) under `namespace`, hashed in parallel.
+fn name_based_batch(
+    py: Python<'_>,
+    kind: &str,
+    make: fn(&uuid::Uuid, &[u8]) -> uuid::Uuid,
+    namespace: &Bound<'_, PyAny>,
+    names: &Bound<'_, PyAny>,
+    returns: &str,
+) -> PyResult<PyObject> {
+    let returns = array::Returns::parse(returns)?;
+    let namespace = uuid::Uuid::from_bytes(uuid_bytes(namespace)?);
+    if names.is_instance_of::<PyString>() || names.is_instance_of::<PyBytes>() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("names must be a sequence of str or bytes, not a single name"));
+    }
+    let names = names.try_iter()?.map(|name| name_bytes(&name?)).collect::<PyResult<Vec<_>>>()?;
+    let ids: Vec<[u8; 16]> =
+        py.allow_threads(|| names.par_iter().map(|name| *make(&namespace, name).as_bytes()).collect());
+    audit::record(py, kind, ids.iter().map(|&bytes| UUID { bytes }))?;
+    returns.build(py, ids)
+}
+
+/// v3 UUIDs for each name under `namespace`, hashed in parallel and returned
+/// in input order; matches `uuid.uuid3(namespace, name)` for every name.
+#[pyfunction]
+#[pyo3(signature = (namespace, names, returns="list"))]
+fn uuid3_batch(py: Python<'_>, namespace: &Bound<'_, PyAny>, names: &Bound<'_, PyAny>, returns: &str) -> PyResult<PyObject> {
+    name_based_batch(py, "uuid3", uuid::Uuid::new_v3, namespace, names, returns)
+}
+
+/// v5 UUIDs for each name under `namespace`, hashed in parallel and returned
+/// in input order; matches `uuid.uuid5(namespace, name)` for every name.
+#[pyfunction]
+#[pyo3(signature = (namespace, names, returns="list"))]
+fn uuid5_batch(py: Python<'_>, namespace: &Bound<'_, PyAny>, names: &Bound<'_, PyAny>, returns: &str) -> PyResult<PyObject> {
+    name_based_batch(py, "uuid5", uuid::Uuid::new_v5, namespace, names, returns)
+}
+
+/// v7 UUIDs paired with their canonical strings, formatted once in Rust.
+#[pyfunction]
+fn uuid7_with_str_batch(py: Python<'_>, count: usize) -> PyResult<Vec<(UUID, String)>> {
+    let pairs: Vec<(UUID, String)> = py.allow_threads(|| {
+        (0..count)
+            .into_par_iter()
+            .map(|_| {
+                let bytes = *uuid::Uuid::now_v7().as_bytes();
+                (UUID { bytes }, canonical_string(&bytes))
+            })
+            .collect()
+    });
+    audit::record(py, "uuid7", pairs.iter().map(|(id, _)| *id))?;
+    Ok(pairs)
+}
+
+/// Base64 over `alphabets::SORTABLE64`: big-endian 6-bit groups in ASCII
+/// order, so encodings of equal-length inputs sort like the inputs.
+const SORTABLE_NO_PAD: base64::engine::GeneralPurpose = match base64::alphabet::Alphabet::new(alphabets::SORTABLE64) {
+    Ok(alphabet) => base64::engine::GeneralPurpose::new(&alphabet, base64::engine::general_purpose::NO_PAD),
+    Err(_) => panic!("SORTABLE64 is a valid base64 alphabet"),
+};
+
+fn short_id_string(bytes: &[u8; 16], full: bool, sortable: bool) -> String {
+    let engine = if sortable { &SORTABLE_NO_PAD } else { &URL_SAFE_NO_PAD };
+    if full {
+        engine.encode(bytes)
+    } else {
+        engine.encode(&bytes[0..12])  // Use 12 bytes for better uniqueness
+    }
+}
+
+fn short_id_source(monotonic: bool) -> [u8; 16] {
+    if monotonic {
+        monotonic::next_shared()
+    } else {
+        *uuid::Uuid::now_v7().as_bytes()
+    }
+}
+
+/// Short URL-safe ID from a new v7 UUID: 16 characters by default, or with
+/// `full=True` the whole UUID in 22 characters, reversible with
+/// `decode_short_id`.
+///
+/// With `monotonic=True` the v7 comes from the process-wide counter shared
+/// with `sortable_key`, so IDs from all threads are distinct and their
+/// underlying values strictly increase, even within one millisecond. (The
+/// base64url text itself does not sort in the same order.)
+///
+/// With `sortable=True` the same bits are written with the ASCII-ordered
+/// `alphabets.SORTABLE64`, so that IDs compared as strings sort by creation
+/// time, e.g. in a text column with binary ("C") collation;
+/// locale-aware collations that ignore case or punctuation break the order.
+#[pyfunction]
+#[pyo3(signature = (full=false, monotonic=false, sortable=false))]
+fn short_id(py: Python<'_>, full: bool, monotonic: bool, sortable: bool) -> PyResult<String> {
+    let id = short_id_string(&short_id_source(monotonic), full, sortable);
+    audit::record(py, "short_id", [&id])?;
+    Ok(id)
+}
+
+/// Random v4 UUID as 22 base58 characters, as `uuid4().base58()`; the
+/// alphabet avoids look-alike symbols and URL-reserved characters.
+#[pyfunction]
+fn base58_id(py: Python<'_>) -> PyResult<String> {
+    let id = UUID { bytes: *uuid::Uuid::new_v4().as_bytes() }.base58();
+    audit::record(py, "base58_id", [&id])?;
+    Ok(id)
+}
+
+#[pyfunction]
+#[pyo3(signature = (count, full=false, monotonic=false, unique=false, sortable=false))]
+fn short_id_batch(
+    py: Python<'_>,
+    count: usize,
+    full: bool,
+    monotonic: bool,
+    unique: bool,
+    sortable: bool,
+) -> PyResult<Vec<String>> {
+    let ids: Vec<String> = py.allow_threads(|| {
+        if monotonic {
+            // Sequential: the shared counter would serialize parallel workers
+            // anyway, and its IDs are always distinct.
+            return (0..count).map(|_| short_id_string(&monotonic::next_shared(), full, sortable)).collect();
+        }
+        let generate = || short_id_string(uuid::Uuid::now_v7().as_bytes(), full, sortable);
+        let mut ids: Vec<String> = (0..count).into_par_iter().map(|_| generate()).collect();
+        if unique {
+            dedupe(&mut ids, generate);
+        }
+        ids
+    });
+    audit::record(py, "short_id", &ids)?;
+    Ok(ids)
+}
+
+/// The UUID behind a 22-character `short_id(full=True)`, with the same
+/// `sortable`.
+#[pyfunction]
+#[pyo3(signature = (s, sortable=false))]
+fn decode_short_id(s: &str, sortable: bool) -> PyResult<UUID> {
+    UUID::from_short_id(s, sortable)
+}
+
+/// Uniform random value below `n` from the OS CSPRNG.
+pub(crate) fn secure_below(n: u32) -> u32 {
+    let zone = u32::MAX - u32::MAX % n;
+    loop {
+        let v = getrandom::u32().expect("OS random source is available");
+        if v < zone {
+            return v % n;
+        }
+    }
+}
+
+pub(crate) fn random_string(size: usize, symbols: &[char]) -> String {
+    (0..size).map(|_| symbols[fastrand::usize(0..symbols.len())]).collect()
+}
+
+/// `random_string` from the OS CSPRNG. Random bytes are masked to the next
+/// power of two and out-of-range values rejected, as the reference nanoid
+/// does, so every symbol is equally likely.
+fn secure_string(size: usize, symbols: &[char]) -> String {
+    let n = symbols.len();
+    if n > 256 {
+        return (0..size).map(|_| symbols[secure_below(n as u32) as usize]).collect();
+    }
+    let mask = (n.next_power_of_two() - 1) as u8;
+    let mut out = String::with_capacity(size);
+    let mut remaining = size;
+    let mut buf = vec![0u8; size + size / 2 + 1];
+    while remaining > 0 {
+        getrandom::fill(&mut buf).expect("OS random source is available");
+        for &b in buf.iter().filter(|&&b| ((b & mask) as usize) < n).take(remaining) {
+            out.push(symbols[(b & mask) as usize]);
+            remaining -= 1;
+        }
+    }
+    out
+}
+
+pub(crate) fn nano_id_string(size: usize, symbols: &[char], secure: bool) -> String {
+    if secure {
+        secure_string(size, symbols)
+    } else {
+        random_string(size, symbols)
     }
-    result
 }
 
+fn nano_id_symbols(alphabet: Option<&str>) -> PyResult<Vec<char>> {
+    let alphabet = encoding::Alphabet::new(alphabet.unwrap_or(alphabets::URL_SAFE))?;
+    Ok(alphabet.symbols().to_vec())
+}
+
+/// Random ID of `size` (default 21) symbols from `alphabet`.
+///
+/// By default the symbols come from the OS CSPRNG, so IDs are safe to use as
+/// unguessable tokens such as session IDs or reset links. `secure=False`
+/// uses the faster non-cryptographic generator, for IDs that only need to be
+/// unique.
 #[pyfunction]
-#[pyo3(signature = (count, size=None))]
-fn nano_id_batch(count: usize, size: Option<usize>) -> Vec<String> {
+#[pyo3(signature = (size=None, alphabet=None, secure=true))]
+fn nano_id(py: Python<'_>, size: Option<usize>, alphabet: Option<&str>, secure: bool) -> PyResult<String> {
+    let id = nano_id_string(size.unwrap_or(21), &nano_id_symbols(alphabet)?, secure);
+    audit::record(py, "nano_id", [&id])?;
+    Ok(id)
+}
+
+#[pyfunction]
+#[pyo3(signature = (count, size=None, alphabet=None, secure=true, unique=false))]
+fn nano_id_batch(
+    py: Python<'_>,
+    count: usize,
+    size: Option<usize>,
+    alphabet: Option<&str>,
+    secure: bool,
+    unique: bool,
+) -> PyResult<Vec<String>> {
     let size = size.unwrap_or(21);
-    (0..count)
-        .into_par_iter()
-        .map(|_| nano_id(Some(size)))
-        .collect()
+    let symbols = nano_id_symbols(alphabet)?;
+    if unique && (symbols.len() as f64).powi(size.min(i32::MAX as usize) as i32) < count as f64 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{} distinct IDs requested, but size {} allows fewer",
+            count, size
+        )));
+    }
+    let ids: Vec<String> = py.allow_threads(|| {
+        let generate = || nano_id_string(size, &symbols, secure);
+        let mut ids: Vec<String> = (0..count).into_par_iter().map(|_| generate()).collect();
+        if unique {
+            dedupe(&mut ids, generate);
+        }
+        ids
+    });
+    audit::record(py, "nano_id", &ids)?;
+    Ok(ids)
 }
 
 #[pymodule]
 fn rustid(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<UUID>()?;
+    m.add_class::<stream::IdStream>()?;
+    m.add_class::<arrow::ArrowIdStream>()?;
+    m.add_class::<arrow::ArrowIdArray>()?;
+    m.add_class::<sequence::CompactIdGenerator>()?;
+    m.add_class::<sequence::Snowflake>()?;
+    m.add_class::<array::UuidArray>()?;
+    m.add_class::<reader::IdFileReader>()?;
+    m.add_class::<reader::BinaryIdReader>()?;
+    m.add_class::<idset::IdSet>()?;
+    m.add_class::<ulid::Ulid>()?;
+    m.add_class::<ulid::MonotonicUlidFactory>()?;
+    m.add_class::<typeid::TypeId>()?;
+    m.add_class::<registry::PrefixRegistry>()?;
     m.add_function(wrap_pyfunction!(uuid1, m)?)?;
     m.add_function(wrap_pyfunction!(uuid4, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid6, m)?)?;
     m.add_function(wrap_pyfunction!(uuid7, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid3, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid5, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid8, m)?)?;
     m.add_function(wrap_pyfunction!(uuid4_batch, m)?)?;
     m.add_function(wrap_pyfunction!(uuid7_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid7_batch_partition, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid7_monotonic, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid7_monotonic_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid6_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid3_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid5_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(uuid7_with_str_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(packed::uuid_bytes_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(packed::uuid4_bytes_bulk, m)?)?;
+    m.add_function(wrap_pyfunction!(packed::uuid7_bytes_bulk, m)?)?;
+    m.add_function(wrap_pyfunction!(packed::uuid4_fill, m)?)?;
+    m.add_function(wrap_pyfunction!(packed::uuid7_fill, m)?)?;
+    m.add_function(wrap_pyfunction!(index::write_index, m)?)?;
+    m.add_function(wrap_pyfunction!(index::contains_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(derive::uuid7_seq, m)?)?;
+    m.add_function(wrap_pyfunction!(derive::derive_uuid, m)?)?;
+    m.add_function(wrap_pyfunction!(derive::idempotency_key, m)?)?;
     m.add_function(wrap_pyfunction!(short_id, m)?)?;
     m.add_function(wrap_pyfunction!(short_id_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(base58_id, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_short_id, m)?)?;
     m.add_function(wrap_pyfunction!(nano_id, m)?)?;
     m.add_function(wrap_pyfunction!(nano_id_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(cuid::cuid2, m)?)?;
+    m.add_function(wrap_pyfunction!(cuid::cuid2_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow::arrow_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow::uuid4_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(arrow::uuid7_arrow, m)?)?;
+    m.add_function(wrap_pyfunction!(columns::generate_columns, m)?)?;
+    m.add_function(wrap_pyfunction!(writer::write_copy, m)?)?;
+    m.add_function(wrap_pyfunction!(checkdigit::add_check_digit, m)?)?;
+    m.add_function(wrap_pyfunction!(checkdigit::verify_check_digit, m)?)?;
+    m.add_function(wrap_pyfunction!(coupon::coupon_code, m)?)?;
+    m.add_function(wrap_pyfunction!(coupon::coupon_code_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(coupon::is_valid_coupon_code, m)?)?;
+    m.add_function(wrap_pyfunction!(codename::codename, m)?)?;
+    m.add_function(wrap_pyfunction!(codename::to_color, m)?)?;
+    m.add_function(wrap_pyfunction!(codename::to_identicon_seed, m)?)?;
+    m.add_function(wrap_pyfunction!(compare::distance, m)?)?;
+    m.add_function(wrap_pyfunction!(compare::prefix_match_len, m)?)?;
+    m.add_function(wrap_pyfunction!(compare::is_monotonic, m)?)?;
+    m.add_function(wrap_pyfunction!(compare::first_violation, m)?)?;
+    m.add_function(wrap_pyfunction!(vectors::vectors, m)?)?;
+    m.add_function(wrap_pyfunction!(encoding::recode, m)?)?;
+    m.add_function(wrap_pyfunction!(encoding::encode, m)?)?;
+    m.add_function(wrap_pyfunction!(encoding::decode, m)?)?;
+    m.add("NAMESPACE_DNS", UUID { bytes: *uuid::Uuid::NAMESPACE_DNS.as_bytes() })?;
+    m.add("NAMESPACE_URL", UUID { bytes: *uuid::Uuid::NAMESPACE_URL.as_bytes() })?;
+    m.add("NAMESPACE_OID", UUID { bytes: *uuid::Uuid::NAMESPACE_OID.as_bytes() })?;
+    m.add("NAMESPACE_X500", UUID { bytes: *uuid::Uuid::NAMESPACE_X500.as_bytes() })?;
+    alphabets::register(m)?;
+    m.add_function(wrap_pyfunction!(migrate::upgrade_to_v7, m)?)?;
+    m.add_function(wrap_pyfunction!(migrate::rewrap_random_as_v4, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::is_valid, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::normalize_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::parse_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::validate, m)?)?;
+    m.add_function(wrap_pyfunction!(timestamp::detect_anomalies, m)?)?;
+    m.add_function(wrap_pyfunction!(shard::route_by_time, m)?)?;
+    m.add_function(wrap_pyfunction!(shard::time_window, m)?)?;
+    m.add_function(wrap_pyfunction!(expiry::expiring_id, m)?)?;
+    m.add_function(wrap_pyfunction!(expiry::expires_at, m)?)?;
+    m.add_function(wrap_pyfunction!(expiry::is_expired, m)?)?;
+    m.add_function(wrap_pyfunction!(region::region_id, m)?)?;
+    m.add_function(wrap_pyfunction!(region::region_of, m)?)?;
+    m.add_function(wrap_pyfunction!(parse::validate_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(reader::read_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(keys::sortable_key, m)?)?;
+    m.add_function(wrap_pyfunction!(keys::parse_sortable_key, m)?)?;
+    m.add_function(wrap_pyfunction!(keys::salted_key, m)?)?;
+    m.add_function(wrap_pyfunction!(keys::unsalt_key, m)?)?;
+    m.add_function(wrap_pyfunction!(context::new_request_id, m)?)?;
+    m.add_function(wrap_pyfunction!(context::current_request_id, m)?)?;
+    m.add_function(wrap_pyfunction!(context::set_request_id, m)?)?;
+    m.add_function(wrap_pyfunction!(context::reset_request_id, m)?)?;
+    m.add_function(wrap_pyfunction!(trace::trace_id, m)?)?;
+    m.add_function(wrap_pyfunction!(trace::span_id, m)?)?;
+    m.add_function(wrap_pyfunction!(trace::trace_id_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(trace::span_id_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(trace::should_sample, m)?)?;
+    m.add_function(wrap_pyfunction!(testdata::uuid7_backfill_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(testdata::sample_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(testdata::simulate_collisions, m)?)?;
+    m.add_function(wrap_pyfunction!(pseudonymize::pseudonymize_file, m)?)?;
+    m.add_function(wrap_pyfunction!(pseudonymize::pseudonymize_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(pseudonymize::rekey, m)?)?;
+    m.add_function(wrap_pyfunction!(sequence::find_gaps, m)?)?;
+    m.add_function(wrap_pyfunction!(intern::intern_strings, m)?)?;
+    m.add_function(wrap_pyfunction!(hashing::hash_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(hashing::set_hash_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(hashing::get_hash_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(compare::set_strict_equality, m)?)?;
+    m.add_function(wrap_pyfunction!(format::format_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(format::set_uppercase, m)?)?;
+    m.add_function(wrap_pyfunction!(format::group, m)?)?;
+    m.add_function(wrap_pyfunction!(format::ungroup, m)?)?;
+    m.add_function(wrap_pyfunction!(selfcheck::self_check, m)?)?;
+    m.add_function(wrap_pyfunction!(audit::set_audit_hook, m)?)?;
+    m.add_function(wrap_pyfunction!(codec::pack_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(codec::unpack_ids, m)?)?;
+    m.add_function(wrap_pyfunction!(typeid::typeid, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::ulid, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::ulid_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::ulid_str_to_uuid_str, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::uuid_str_to_ulid_str, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::ulid_str_to_uuid_str_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(ulid::uuid_str_to_ulid_str_batch, m)?)?;
+    #[cfg(feature = "parquet")]
+    m.add_function(wrap_pyfunction!(writer::write_parquet, m)?)?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+//! Helpers for moving historical keys between UUID versions.
+
+use pyo3::prelude::*;
+
+use crate::timestamp::unix_ticks;
+use crate::UUID;
+
+/// Rewrite a v1 or v6 ID as v7 with the same creation time.
+///
+/// The millisecond goes into `unix_ts_ms`, the sub-millisecond remainder is
+/// scaled into `rand_a` (RFC 9562, section 6.2, method 3), and the 14-bit
+/// clock sequence plus 48-bit node fill `rand_b`. The mapping is
+/// deterministic, so re-running a migration yields the same keys, and the
+/// resulting IDs sort in the original time order.
+#[pyfunction]
+pub fn upgrade_to_v7(id: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let src = crate::uuid_bytes(id)?;
+    let ticks = unix_ticks(&src).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Expected a v1 or v6 UUID, got version {}", src[6] >> 4))
+    })?;
+    let ms = ticks / 10_000;
+    let sub_ms = ((ticks % 10_000) * 4096 / 10_000) as u16;
+
+    let mut bytes = [0u8; 16];
+    bytes[0..6].copy_from_slice(&ms.to_be_bytes()[2..]);
+    bytes[6] = 0x70 | (sub_ms >> 8) as u8;
+    bytes[7] = sub_ms as u8;
+    bytes[8..].copy_from_slice(&src[8..]);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Ok(UUID { bytes })
+}
+
+/// Stamp version 4 and the RFC variant onto 16 caller-supplied random bytes.
+#[pyfunction]
+pub fn rewrap_random_as_v4(data: &Bound<'_, PyAny>) -> PyResult<UUID> {
+    let mut bytes = crate::uuid_bytes(data)?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Ok(UUID { bytes })
+}
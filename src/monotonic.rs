@@ -0,0 +1,160 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use pyo3::prelude::*;
+
+const COUNTER_MAX: u16 = 0x0fff;
+// Fresh milliseconds seed the counter below this value so there is always
+// headroom left for increments within the same millisecond.
+const COUNTER_SEED_MAX: u16 = 0x0800;
+
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// What a monotonic generator does when its per-millisecond counter runs out.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) enum Overflow {
+    /// Sleep until the clock reaches the next millisecond.
+    Wait,
+    /// Continue in the next millisecond right away, running slightly ahead of
+    /// the clock until it catches up.
+    #[default]
+    Borrow,
+    /// Raise `OverflowError`.
+    Raise,
+}
+
+impl Overflow {
+    pub(crate) fn parse(policy: &str) -> PyResult<Self> {
+        match policy {
+            "wait" => Ok(Overflow::Wait),
+            "borrow" => Ok(Overflow::Borrow),
+            "raise" => Ok(Overflow::Raise),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported overflow policy: {}", policy))),
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Overflow::Wait => "wait",
+            Overflow::Borrow => "borrow",
+            Overflow::Raise => "raise",
+        }
+    }
+
+    /// Apply the policy once `step(now)` has reported an exhausted counter:
+    /// `Ok(true)` to borrow the next millisecond, `Ok(false)` to retry.
+    pub(crate) fn on_exhausted(self) -> PyResult<bool> {
+        match self {
+            Overflow::Wait => {
+                std::thread::sleep(Duration::from_micros(100));
+                Ok(false)
+            }
+            Overflow::Borrow => Ok(true),
+            Overflow::Raise => Err(PyErr::new::<pyo3::exceptions::PyOverflowError, _>(
+                "Monotonic counter exhausted within one millisecond",
+            )),
+        }
+    }
+}
+
+/// Monotonic UUIDv7 source using a dedicated 12-bit counter in `rand_a`
+/// (RFC 9562, section 6.2, method 1).
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Uuid7Counter {
+    ms: u64,
+    counter: u16,
+}
+
+impl Uuid7Counter {
+    pub(crate) const fn new() -> Self {
+        Uuid7Counter { ms: 0, counter: 0 }
+    }
+
+    /// Rebuild the counter from the last ID that was handed out, so the next
+    /// value sorts strictly after it.
+    pub(crate) fn resume(last: &[u8; 16]) -> Self {
+        let mut ms = [0u8; 8];
+        ms[2..].copy_from_slice(&last[0..6]);
+        Uuid7Counter {
+            ms: u64::from_be_bytes(ms),
+            counter: u16::from_be_bytes([last[6] & 0x0f, last[7]]),
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> [u8; 16] {
+        self.next_at(now_ms())
+    }
+
+    /// Next ID as if the clock read `now`; used to backfill historical ranges.
+    /// An exhausted counter borrows the next millisecond.
+    pub(crate) fn next_at(&mut self, now: u64) -> [u8; 16] {
+        if !self.step(now) {
+            self.borrow();
+        }
+        self.encode()
+    }
+
+    /// Next ID, handling counter exhaustion according to `overflow`.
+    pub(crate) fn next_with(&mut self, overflow: Overflow) -> PyResult<[u8; 16]> {
+        while !self.step(now_ms()) {
+            if overflow.on_exhausted()? {
+                self.borrow();
+                break;
+            }
+        }
+        Ok(self.encode())
+    }
+
+    fn borrow(&mut self) {
+        self.ms += 1;
+        self.counter = fastrand::u16(0..COUNTER_SEED_MAX);
+    }
+
+    /// Advance to `now`; `false` if the counter for the current millisecond
+    /// is exhausted and nothing changed.
+    fn step(&mut self, now: u64) -> bool {
+        if now > self.ms {
+            self.ms = now;
+            self.counter = fastrand::u16(0..COUNTER_SEED_MAX);
+        } else if self.counter < COUNTER_MAX {
+            self.counter += 1;
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn encode(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[0..6].copy_from_slice(&self.ms.to_be_bytes()[2..]);
+        bytes[6] = 0x70 | (self.counter >> 8) as u8;
+        bytes[7] = self.counter as u8;
+        bytes[8..].copy_from_slice(&fastrand::u64(..).to_be_bytes());
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+        bytes
+    }
+}
+
+static SHARED: Mutex<Uuid7Counter> = Mutex::new(Uuid7Counter::new());
+
+/// Next ID from the process-wide counter, ordered across all callers and threads.
+pub(crate) fn next_shared() -> [u8; 16] {
+    SHARED.lock().unwrap_or_else(|e| e.into_inner()).next()
+}
+
+/// `next_shared`, handling counter exhaustion according to `overflow`.
+pub(crate) fn next_shared_with(overflow: Overflow) -> PyResult<[u8; 16]> {
+    SHARED.lock().unwrap_or_else(|e| e.into_inner()).next_with(overflow)
+}
+
+/// `count` consecutive IDs from the process-wide counter, taken under one
+/// lock so no other caller's IDs fall between them.
+pub(crate) fn next_shared_batch(count: usize, overflow: Overflow) -> PyResult<Vec<[u8; 16]>> {
+    let mut counter = SHARED.lock().unwrap_or_else(|e| e.into_inner());
+    (0..count).map(|_| counter.next_with(overflow)).collect()
+}
@@ -0,0 +1,141 @@
+//! Flat byte output of generated IDs.
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use rayon::prelude::*;
+
+use crate::audit;
+use crate::buffer::RawBuffer;
+use crate::columns::ColumnKind;
+use crate::UUID;
+
+/// Memory layout of packed IDs.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// Array of structs: one 16-byte big-endian row per ID.
+    Rows,
+    /// Struct of arrays: every ID's high 64 bits, then every ID's low 64
+    /// bits, each as a little-endian `u64`.
+    Planar,
+}
+
+impl Layout {
+    pub(crate) fn parse(layout: &str) -> PyResult<Self> {
+        match layout {
+            "aos" | "rows" => Ok(Layout::Rows),
+            "soa" | "planar" => Ok(Layout::Planar),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported layout: {}", layout))),
+        }
+    }
+
+    /// Write `ids` into `out`, which must be exactly `ids.len() * 16` bytes.
+    pub(crate) fn write(self, ids: &[[u8; 16]], out: &mut [u8]) {
+        match self {
+            Layout::Rows => {
+                for (row, id) in out.chunks_exact_mut(16).zip(ids) {
+                    row.copy_from_slice(id);
+                }
+            }
+            Layout::Planar => {
+                let (high, low) = out.split_at_mut(ids.len() * 8);
+                for ((h, l), id) in high.chunks_exact_mut(8).zip(low.chunks_exact_mut(8)).zip(ids) {
+                    let value = u128::from_be_bytes(*id);
+                    h.copy_from_slice(&((value >> 64) as u64).to_le_bytes());
+                    l.copy_from_slice(&(value as u64).to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// `count` generated UUIDs as one `bytes` object of `count * 16` bytes.
+///
+/// `layout="aos"` gives 16-byte rows; `layout="soa"` gives two planes of
+/// little-endian `u64` (all high halves, then all low halves), readable with
+/// `np.frombuffer(buf, "<u8").reshape(2, count)`.
+#[pyfunction]
+#[pyo3(signature = (count, kind="uuid7", layout="aos"))]
+pub fn uuid_bytes_batch<'py>(py: Python<'py>, count: usize, kind: &str, layout: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let kind = ColumnKind::parse(kind)?;
+    let layout = Layout::parse(layout)?;
+    let ids = py
+        .allow_threads(|| kind.generate_bytes(count))
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Packed output requires a UUID kind"))?;
+    audit::record(py, kind.name(), ids.iter().map(|&bytes| UUID { bytes }))?;
+    PyBytes::new_with(py, count * 16, |out| {
+        layout.write(&ids, out);
+        Ok(())
+    })
+}
+
+/// Fill every 16-byte row of `out` from `generate`, in parallel and without
+/// the GIL.
+fn fill_rows(py: Python<'_>, out: &mut [u8], generate: impl Fn() -> [u8; 16] + Sync) {
+    py.allow_threads(|| out.par_chunks_exact_mut(16).for_each(|row| row.copy_from_slice(&generate())));
+}
+
+fn record_rows(py: Python<'_>, kind: &str, rows: &[u8]) -> PyResult<()> {
+    let rows = rows.chunks_exact(16);
+    audit::record(py, kind, rows.map(|row| UUID { bytes: row.try_into().expect("16-byte rows") }))
+}
+
+/// `count` IDs from `generate`, written straight into one `bytes` object
+/// without an intermediate buffer.
+fn bulk<'py>(
+    py: Python<'py>,
+    count: usize,
+    kind: &str,
+    generate: impl Fn() -> [u8; 16] + Sync,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let out = PyBytes::new_with(py, count * 16, |out| {
+        fill_rows(py, out, generate);
+        Ok(())
+    })?;
+    record_rows(py, kind, out.as_bytes())?;
+    Ok(out)
+}
+
+/// Fill the writable, C-contiguous `buffer` with packed IDs from `generate`;
+/// returns the number of IDs written.
+fn fill(py: Python<'_>, buffer: &Bound<'_, PyAny>, kind: &str, generate: impl Fn() -> [u8; 16] + Sync) -> PyResult<usize> {
+    let mut buffer = RawBuffer::get(buffer, true)?;
+    let out = buffer.as_mut_slice();
+    if out.len() % 16 != 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Buffer size must be a multiple of 16 bytes, got {}",
+            out.len()
+        )));
+    }
+    fill_rows(py, out, generate);
+    record_rows(py, kind, out)?;
+    Ok(out.len() / 16)
+}
+
+/// `count` v4 UUIDs as one `bytes` object of `count * 16` bytes, for slicing
+/// or feeding to a binary protocol without creating a `UUID` per ID.
+#[pyfunction]
+pub fn uuid4_bytes_bulk(py: Python<'_>, count: usize) -> PyResult<Bound<'_, PyBytes>> {
+    bulk(py, count, "uuid4", || *uuid::Uuid::new_v4().as_bytes())
+}
+
+/// `count` v7 UUIDs as one `bytes` object of `count * 16` bytes.
+#[pyfunction]
+pub fn uuid7_bytes_bulk(py: Python<'_>, count: usize) -> PyResult<Bound<'_, PyBytes>> {
+    bulk(py, count, "uuid7", || *uuid::Uuid::now_v7().as_bytes())
+}
+
+/// Fill a writable buffer (`bytearray`, numpy array, `mmap`, shared memory,
+/// ...) with packed 16-byte v4 UUIDs, generated in parallel in place.
+/// The buffer must be C-contiguous and a multiple of 16 bytes long. Returns
+/// the number of IDs written.
+#[pyfunction]
+pub fn uuid4_fill(py: Python<'_>, buffer: &Bound<'_, PyAny>) -> PyResult<usize> {
+    fill(py, buffer, "uuid4", || *uuid::Uuid::new_v4().as_bytes())
+}
+
+/// Fill a writable buffer with packed 16-byte v7 UUIDs; see `uuid4_fill`.
+/// Rows are filled in parallel, so they are not in creation order.
+#[pyfunction]
+pub fn uuid7_fill(py: Python<'_>, buffer: &Bound<'_, PyAny>) -> PyResult<usize> {
+    fill(py, buffer, "uuid7", || *uuid::Uuid::now_v7().as_bytes())
+}
@@ -0,0 +1,289 @@
+//! Lenient parsing of the textual UUID forms found in third-party data.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+use rayon::prelude::*;
+
+use crate::buffer::RawBuffer;
+use crate::monotonic::now_ms;
+use crate::timestamp::unix_ms_of;
+use crate::UUID;
+
+/// Parse canonical, bare 32-hex, `urn:uuid:`-prefixed, or braced forms, in any case.
+pub(crate) fn parse_lenient(s: &[u8]) -> Result<[u8; 16], &'static str> {
+    let mut raw = s.trim_ascii();
+    if raw.len() >= 9 && raw[..9].eq_ignore_ascii_case(b"urn:uuid:") {
+        raw = &raw[9..];
+    }
+    if let Some(inner) = raw.strip_prefix(b"{") {
+        raw = inner.strip_suffix(b"}").ok_or("unbalanced braces")?;
+    }
+    let mut hex = [0u8; 32];
+    match raw.len() {
+        32 => hex.copy_from_slice(raw),
+        36 => {
+            if raw[8] != b'-' || raw[13] != b'-' || raw[18] != b'-' || raw[23] != b'-' {
+                return Err("misplaced dashes");
+            }
+            let mut pos = 0;
+            for (i, &c) in raw.iter().enumerate() {
+                if !matches!(i, 8 | 13 | 18 | 23) {
+                    hex[pos] = c;
+                    pos += 1;
+                }
+            }
+        }
+        _ => return Err("expected 32 hex digits"),
+    }
+    let mut bytes = [0u8; 16];
+    hex::decode_to_slice(hex, &mut bytes).map_err(|_| "invalid hex digit")?;
+    Ok(bytes)
+}
+
+/// Parse one textual ID in any form `parse_lenient` accepts: canonical,
+/// bare 32-hex, `urn:uuid:`-prefixed or braced, in any case. Invalid input
+/// raises `ValueError` saying what is wrong with it.
+#[pyfunction]
+pub fn parse(s: &str) -> PyResult<UUID> {
+    parse_lenient(s.as_bytes()).map(|bytes| UUID { bytes }).map_err(|reason| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UUID: {:?} ({})", s, reason))
+    })
+}
+
+/// Whether `parse(s)` would succeed.
+#[pyfunction]
+pub fn is_valid(s: &str) -> bool {
+    parse_lenient(s.as_bytes()).is_ok()
+}
+
+/// Normalize IDs to lowercase canonical form in parallel, whatever
+/// `set_uppercase` is set to, so normalized keys always compare equal.
+///
+/// With `errors="raise"` the first invalid entry raises `ValueError` naming
+/// its index; with `errors="none"` invalid entries become `None`.
+#[pyfunction]
+#[pyo3(signature = (ids, errors="raise"))]
+pub fn normalize_batch(py: Python<'_>, ids: Vec<String>, errors: &str) -> PyResult<Vec<Option<String>>> {
+    let raise = match errors {
+        "raise" => true,
+        "none" => false,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported errors mode: {}", errors))),
+    };
+    let parsed: Vec<Result<String, &'static str>> = py.allow_threads(|| {
+        ids.par_iter()
+            .map(|s| parse_lenient(s.as_bytes()).map(|bytes| uuid::Uuid::from_bytes(bytes).hyphenated().to_string()))
+            .collect()
+    });
+    parsed
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| match result {
+            Ok(s) => Ok(Some(s)),
+            Err(reason) if raise => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid UUID at index {}: {:?} ({})",
+                i, ids[i], reason
+            ))),
+            Err(_) => Ok(None),
+        })
+        .collect()
+}
+
+/// Borrowed byte-string items of a batch input: the fixed-width items of a
+/// buffer such as a numpy `S32`/`S36` array (NUL padding stripped), or the
+/// str/bytes items of any other iterable, such as a list or an object array.
+enum BatchItems<'a> {
+    Fixed { data: &'a [u8], width: usize },
+    Slices(Vec<&'a [u8]>),
+}
+
+impl BatchItems<'_> {
+    fn par_map<T: Send>(&self, f: impl Fn(&[u8]) -> T + Sync + Send) -> Vec<T> {
+        match self {
+            BatchItems::Fixed { data, width } => data
+                .par_chunks(*width)
+                .map(|item| {
+                    let end = item.iter().rposition(|&c| c != 0).map_or(0, |i| i + 1);
+                    f(&item[..end])
+                })
+                .collect(),
+            BatchItems::Slices(items) => items.par_iter().map(|item| f(item)).collect(),
+        }
+    }
+
+    fn get(&self, i: usize) -> &[u8] {
+        match self {
+            BatchItems::Fixed { data, width } => &data[i * width..(i + 1) * width],
+            BatchItems::Slices(items) => items[i],
+        }
+    }
+}
+
+/// Keeps whatever backs `BatchItems` alive while it is borrowed.
+enum BatchSource<'py> {
+    Buffer(RawBuffer),
+    Objects(Vec<Bound<'py, PyAny>>),
+}
+
+impl<'py> BatchSource<'py> {
+    fn new(ids: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if !ids.is_instance_of::<PyString>() && !ids.is_instance_of::<PyBytes>() {
+            if let Ok(buffer) = RawBuffer::get(ids, false) {
+                if buffer.is_fixed_bytes() && buffer.itemsize() > 0 {
+                    return Ok(BatchSource::Buffer(buffer));
+                }
+            }
+        }
+        Ok(BatchSource::Objects(ids.try_iter()?.collect::<PyResult<_>>()?))
+    }
+
+    fn items(&self) -> PyResult<BatchItems<'_>> {
+        match self {
+            BatchSource::Buffer(buffer) => Ok(BatchItems::Fixed { data: buffer.as_slice(), width: buffer.itemsize() }),
+            BatchSource::Objects(objects) => objects
+                .iter()
+                .map(|item| {
+                    if let Ok(s) = item.downcast::<PyString>() {
+                        Ok(s.to_str()?.as_bytes())
+                    } else if let Ok(b) = item.downcast::<PyBytes>() {
+                        Ok(b.as_bytes())
+                    } else {
+                        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("Expected str or bytes items"))
+                    }
+                })
+                .collect::<PyResult<_>>()
+                .map(BatchItems::Slices),
+        }
+    }
+}
+
+/// Parse a batch of textual IDs in parallel.
+///
+/// `ids` may be any iterable of str/bytes, or a numpy array of fixed-width
+/// bytes (`S32`/`S36`) which is read in place through the buffer protocol.
+///
+/// With `errors="raise"` the first invalid entry raises `ValueError` naming
+/// its index; with `errors="none"` invalid entries become `None`; with
+/// `errors="indices"` the result is a `(uuids, invalid)` pair of the valid
+/// IDs in input order and the indices of the invalid entries, so one bad row
+/// does not fail a whole import.
+#[pyfunction]
+#[pyo3(signature = (ids, errors="raise"))]
+pub fn parse_batch(py: Python<'_>, ids: &Bound<'_, PyAny>, errors: &str) -> PyResult<PyObject> {
+    if !matches!(errors, "raise" | "none" | "indices") {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported errors mode: {}", errors)));
+    }
+    let source = BatchSource::new(ids)?;
+    let items = source.items()?;
+    let parsed = py.allow_threads(|| items.par_map(parse_lenient));
+    match errors {
+        "none" => {
+            let ids: Vec<Option<UUID>> = parsed.into_iter().map(|result| result.ok().map(|bytes| UUID { bytes })).collect();
+            Ok(ids.into_pyobject(py)?.into_any().unbind())
+        }
+        "indices" => {
+            let mut valid = Vec::with_capacity(parsed.len());
+            let mut invalid = Vec::new();
+            for (i, result) in parsed.into_iter().enumerate() {
+                match result {
+                    Ok(bytes) => valid.push(UUID { bytes }),
+                    Err(_) => invalid.push(i),
+                }
+            }
+            Ok((valid, invalid).into_pyobject(py)?.into_any().unbind())
+        }
+        _ => {
+            let ids = parsed
+                .into_iter()
+                .enumerate()
+                .map(|(i, result)| {
+                    result.map(|bytes| UUID { bytes }).map_err(|reason| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Invalid UUID at index {}: {:?} ({})",
+                            i,
+                            String::from_utf8_lossy(items.get(i)),
+                            reason
+                        ))
+                    })
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(ids.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+}
+
+/// How much of RFC 9562 an ID must satisfy, from weakest to strictest.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Level {
+    /// Parses as 128 bits.
+    Format,
+    /// Also has the RFC variant and one of the defined versions 1-8.
+    Version,
+    /// Also has sane version-specific fields: time-based IDs may not be
+    /// further in the future than the tolerance.
+    Strict,
+}
+
+impl Level {
+    fn parse(level: &str) -> PyResult<Self> {
+        match level {
+            "format" => Ok(Level::Format),
+            "version" => Ok(Level::Version),
+            "strict" => Ok(Level::Strict),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported validation level: {}", level))),
+        }
+    }
+}
+
+/// Whether parsed `bytes` meet `level`, with `max_ms` the latest acceptable
+/// creation time in Unix milliseconds.
+fn meets_level(bytes: &[u8; 16], level: Level, max_ms: u64) -> bool {
+    if level == Level::Format {
+        return true;
+    }
+    let version = bytes[6] >> 4;
+    if bytes[8] >> 6 != 0b10 || !(1..=8).contains(&version) {
+        return false;
+    }
+    if level == Level::Version {
+        return true;
+    }
+    unix_ms_of(bytes).is_none_or(|ms| ms <= max_ms)
+}
+
+/// Whether `id` is a valid UUID at `level`.
+///
+/// `"format"` only requires that it parses (any textual form `parse_batch`
+/// accepts, 16 raw bytes, or a `UUID`). `"version"` also requires the RFC
+/// 9562 variant bits and a defined version, so the nil and max UUIDs fail.
+/// `"strict"` also rejects v1, v6 and v7 IDs whose timestamp is more than
+/// `tolerance_ms` in the future.
+#[pyfunction]
+#[pyo3(signature = (id, level="format", tolerance_ms=60_000))]
+pub fn validate(id: &Bound<'_, PyAny>, level: &str, tolerance_ms: u64) -> PyResult<bool> {
+    let level = Level::parse(level)?;
+    let bytes = if let Ok(id) = id.downcast::<UUID>() {
+        Ok(id.get().bytes)
+    } else if let Ok(s) = id.downcast::<PyString>() {
+        parse_lenient(s.to_str()?.as_bytes())
+    } else if let Ok(b) = id.downcast::<PyBytes>() {
+        let b = b.as_bytes();
+        b.try_into().or_else(|_| parse_lenient(b))
+    } else {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("Expected a UUID, str, or bytes"));
+    };
+    Ok(bytes.is_ok_and(|bytes| meets_level(&bytes, level, now_ms().saturating_add(tolerance_ms))))
+}
+
+/// Whether each textual ID in a batch is valid at `level` (see `validate`);
+/// accepts the same inputs as `parse_batch`.
+#[pyfunction]
+#[pyo3(signature = (ids, level="format", tolerance_ms=60_000))]
+pub fn validate_batch(py: Python<'_>, ids: &Bound<'_, PyAny>, level: &str, tolerance_ms: u64) -> PyResult<Vec<bool>> {
+    let level = Level::parse(level)?;
+    let source = BatchSource::new(ids)?;
+    let items = source.items()?;
+    let max_ms = now_ms().saturating_add(tolerance_ms);
+    Ok(py.allow_threads(|| {
+        items.par_map(|item| parse_lenient(item).is_ok_and(|bytes| meets_level(&bytes, level, max_ms)))
+    }))
+}
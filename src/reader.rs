@@ -0,0 +1,222 @@
+//! Streaming readers for files and streams of stored IDs.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use crate::array::UuidArray;
+use crate::buffer::RawBuffer;
+use crate::parse::parse_lenient;
+
+const DEFAULT_CHUNK: usize = 65536;
+const SNIFF_LEN: usize = 512;
+
+#[derive(Clone, Copy)]
+enum FileFormat {
+    Text,
+    Binary,
+}
+
+impl FileFormat {
+    fn parse(format: &str) -> PyResult<Option<Self>> {
+        match format {
+            "auto" => Ok(None),
+            "text" => Ok(Some(FileFormat::Text)),
+            "binary" => Ok(Some(FileFormat::Binary)),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported ID file format: {}", format))),
+        }
+    }
+
+    /// Text if the leading bytes are printable ASCII or whitespace.
+    fn sniff(reader: &mut BufReader<File>) -> std::io::Result<Self> {
+        let head = reader.fill_buf()?;
+        let head = &head[..head.len().min(SNIFF_LEN)];
+        if head.iter().all(|c| c.is_ascii_graphic() || c.is_ascii_whitespace()) {
+            Ok(FileFormat::Text)
+        } else {
+            Ok(FileFormat::Binary)
+        }
+    }
+}
+
+/// Iterator over a file of IDs, yielding `UuidArray` chunks.
+#[pyclass]
+pub struct IdFileReader {
+    reader: BufReader<File>,
+    format: FileFormat,
+    chunk_size: usize,
+    line: usize,
+}
+
+impl IdFileReader {
+    /// Newline-delimited IDs in any form `parse_lenient` accepts; blank lines are skipped.
+    fn read_text(&mut self) -> PyResult<Vec<[u8; 16]>> {
+        let mut lines = Vec::with_capacity(self.chunk_size);
+        let mut buf = Vec::new();
+        while lines.len() < self.chunk_size {
+            buf.clear();
+            if self.reader.read_until(b'\n', &mut buf)? == 0 {
+                break;
+            }
+            self.line += 1;
+            if !buf.trim_ascii().is_empty() {
+                lines.push((self.line, buf.clone()));
+            }
+        }
+        let parsed: Vec<Result<[u8; 16], &'static str>> = lines.par_iter().map(|(_, line)| parse_lenient(line)).collect();
+        parsed
+            .into_iter()
+            .zip(&lines)
+            .map(|(result, (line, text))| {
+                result.map_err(|reason| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Invalid UUID on line {}: {:?} ({})",
+                        line,
+                        String::from_utf8_lossy(text.trim_ascii()),
+                        reason
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Packed 16-byte IDs; a trailing partial ID is an error.
+    fn read_binary(&mut self) -> PyResult<Vec<[u8; 16]>> {
+        let mut data = Vec::with_capacity(self.chunk_size * 16);
+        (&mut self.reader).take((self.chunk_size * 16) as u64).read_to_end(&mut data)?;
+        if data.len() % 16 != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Truncated ID at end of file: {} trailing bytes",
+                data.len() % 16
+            )));
+        }
+        Ok(data.chunks_exact(16).map(|chunk| chunk.try_into().expect("16-byte chunk")).collect())
+    }
+}
+
+#[pymethods]
+impl IdFileReader {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<UuidArray>> {
+        let ids = py.allow_threads(|| match self.format {
+            FileFormat::Text => self.read_text(),
+            FileFormat::Binary => self.read_binary(),
+        })?;
+        Ok((!ids.is_empty()).then(|| UuidArray::new(ids)))
+    }
+}
+
+/// Stream IDs from `path` in chunks of up to `chunk_size`.
+///
+/// `format="text"` reads one ID per line (canonical, bare hex, braced, or
+/// `urn:uuid:`), `"binary"` reads packed 16-byte IDs, and `"auto"` picks
+/// text when the start of the file is printable ASCII.
+#[pyfunction]
+#[pyo3(signature = (path, format="auto", chunk_size=None))]
+pub fn read_ids(path: PathBuf, format: &str, chunk_size: Option<usize>) -> PyResult<IdFileReader> {
+    let format = FileFormat::parse(format)?;
+    let mut reader = BufReader::new(File::open(path)?);
+    let format = match format {
+        Some(format) => format,
+        None => FileFormat::sniff(&mut reader)?,
+    };
+    Ok(IdFileReader { reader, format, chunk_size: chunk_size.unwrap_or(DEFAULT_CHUNK).max(1), line: 0 })
+}
+
+/// Iterator over packed binary IDs from a file object, socket, or buffer,
+/// yielding `UuidArray` chunks of up to `chunk_size` IDs.
+///
+/// Each frame is `width` bytes (at least 16) whose first 16 bytes are the ID.
+/// Short reads are handled: an incomplete frame is kept until the rest
+/// arrives, and each chunk holds the complete frames available so far, so a
+/// listener gets IDs as soon as they arrive rather than when a full chunk
+/// has. Streams are read with `.read(n)`, or `.recv(n)` for sockets; a read
+/// returning `None` (a non-blocking stream with no data) yields an empty
+/// chunk. A partial frame at end of stream raises `ValueError`.
+#[pyclass]
+pub struct BinaryIdReader {
+    stream: Option<(Py<PyAny>, &'static str)>,
+    width: usize,
+    chunk_size: usize,
+    pending: Vec<u8>,
+    eof: bool,
+}
+
+impl BinaryIdReader {
+    /// Read once from the stream; `false` if no data is available right now.
+    fn read_more(&mut self, py: Python<'_>) -> PyResult<bool> {
+        let Some((stream, method)) = &self.stream else {
+            self.eof = true;
+            return Ok(false);
+        };
+        let want = (self.chunk_size * self.width).saturating_sub(self.pending.len()).max(self.width);
+        let data = stream.bind(py).call_method1(*method, (want,))?;
+        if data.is_none() {
+            return Ok(false);
+        }
+        let data = RawBuffer::get(&data, false)?;
+        if data.as_slice().is_empty() {
+            self.eof = true;
+            return Ok(false);
+        }
+        self.pending.extend_from_slice(data.as_slice());
+        Ok(true)
+    }
+}
+
+#[pymethods]
+impl BinaryIdReader {
+    #[new]
+    #[pyo3(signature = (source, width=16, chunk_size=None))]
+    fn new(source: &Bound<'_, PyAny>, width: usize, chunk_size: Option<usize>) -> PyResult<Self> {
+        if width < 16 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("width must be at least 16 bytes"));
+        }
+        let method = ["read", "recv"].into_iter().find(|m| source.hasattr(*m).unwrap_or(false));
+        let (stream, pending) = match method {
+            Some(method) => (Some((source.clone().unbind(), method)), Vec::new()),
+            None => (None, RawBuffer::get(source, false)?.as_slice().to_vec()),
+        };
+        Ok(BinaryIdReader { stream, width, chunk_size: chunk_size.unwrap_or(DEFAULT_CHUNK).max(1), pending, eof: false })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<UuidArray>> {
+        while self.pending.len() < self.width && !self.eof {
+            if !self.read_more(py)? {
+                break;
+            }
+        }
+        let frames = (self.pending.len() / self.width).min(self.chunk_size);
+        if frames == 0 && self.eof {
+            if !self.pending.is_empty() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Truncated ID at end of stream: {} trailing bytes",
+                    self.pending.len()
+                )));
+            }
+            return Ok(None);
+        }
+        let ids = self.pending[..frames * self.width]
+            .chunks_exact(self.width)
+            .map(|frame| frame[..16].try_into().expect("frames hold at least 16 bytes"))
+            .collect();
+        self.pending.drain(..frames * self.width);
+        Ok(Some(UuidArray::new(ids)))
+    }
+
+    /// Bytes of an incomplete frame waiting for the rest to arrive.
+    #[getter]
+    fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
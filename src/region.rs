@@ -0,0 +1,66 @@
+//! Region-coded IDs: v8 UUIDs that name the region or datacenter owning the
+//! record, so routers can direct requests by inspecting only the ID.
+//!
+//! The layout mirrors v7: bits 0-47 hold the creation time in Unix
+//! milliseconds, followed by the version (`8`) and the 12-bit `rand_a`
+//! field, whose top `bits` bits (1 to 12) hold the region code; the rest of
+//! `rand_a`, the RFC variant and 62 bits of `rand_b` are random. IDs sort by
+//! creation time. Writers and readers must agree on `bits`, which is not
+//! stored in the ID.
+
+use pyo3::prelude::*;
+
+use crate::audit;
+use crate::UUID;
+
+fn check_bits(bits: u32) -> PyResult<()> {
+    if !(1..=12).contains(&bits) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("bits must be between 1 and 12"));
+    }
+    Ok(())
+}
+
+pub(crate) fn region_bytes(region: u16, bits: u32) -> [u8; 16] {
+    let mut bytes = *uuid::Uuid::now_v7().as_bytes();
+    let rand_a = u16::from_be_bytes([bytes[6], bytes[7]]) & 0x0fff;
+    let rand_a = region << (12 - bits) | rand_a & (0x0fff >> bits);
+    bytes[6..8].copy_from_slice(&(0x8000 | rand_a).to_be_bytes());
+    bytes
+}
+
+/// New v8 UUID carrying `region`, a code below `2**bits`.
+#[pyfunction]
+#[pyo3(signature = (region, bits=8))]
+pub fn region_id(py: Python<'_>, region: u16, bits: u32) -> PyResult<UUID> {
+    check_bits(bits)?;
+    if region >> bits != 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "region must be below 2**{} ({})",
+            bits,
+            1u32 << bits
+        )));
+    }
+    let id = UUID { bytes: region_bytes(region, bits) };
+    audit::record(py, "region_id", [id])?;
+    Ok(id)
+}
+
+/// Region code of a `region_id` created with the same `bits`.
+#[pyfunction]
+#[pyo3(signature = (id, bits=8))]
+pub fn region_of(id: &Bound<'_, PyAny>, bits: u32) -> PyResult<u16> {
+    check_bits(bits)?;
+    let bytes = crate::uuid_bytes(id)?;
+    let version = bytes[6] >> 4;
+    if version != 8 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "UUID version {} is not a region ID; only version 8 is",
+            version
+        )));
+    }
+    Ok(region_code(&bytes, bits))
+}
+
+pub(crate) fn region_code(bytes: &[u8; 16], bits: u32) -> u16 {
+    (u16::from_be_bytes([bytes[6], bytes[7]]) & 0x0fff) >> (12 - bits)
+}
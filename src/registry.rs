@@ -0,0 +1,125 @@
+//! Registry of reserved ID prefixes, so services cannot silently pick the
+//! same prefix.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+use crate::typeid::TypeId;
+
+fn value_error(msg: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(msg)
+}
+
+/// Comparison form of a prefix: case and trailing separators are ignored, so
+/// `usr`, `usr_` and `USR-` all claim the same name.
+fn normalize(prefix: &str) -> String {
+    prefix.trim_end_matches(['_', '-', ':', '.']).to_lowercase()
+}
+
+/// Reserved prefixes for TypeIDs and prefixed keys, each owned by a team or
+/// service.
+///
+/// Registering a prefix that conflicts with another owner's raises
+/// `ValueError`, as does generating or parsing an ID whose prefix was never
+/// registered. Prefixes conflict when they are equal ignoring case and
+/// trailing `_`, `-`, `:` or `.`.
+#[pyclass]
+#[derive(Default)]
+pub struct PrefixRegistry {
+    /// Normalized prefix to `(prefix, owner)`.
+    entries: HashMap<String, (String, String)>,
+}
+
+impl PrefixRegistry {
+    fn check_registered(&self, prefix: &str) -> PyResult<()> {
+        match self.entries.get(&normalize(prefix)) {
+            Some((registered, _)) if registered == prefix => Ok(()),
+            Some((registered, owner)) => Err(value_error(format!(
+                "Prefix {:?} is not registered; did you mean {:?} (owned by {:?})?",
+                prefix, registered, owner
+            ))),
+            None => Err(value_error(format!("Prefix {:?} is not registered", prefix))),
+        }
+    }
+}
+
+#[pymethods]
+impl PrefixRegistry {
+    #[new]
+    fn new() -> Self {
+        PrefixRegistry::default()
+    }
+
+    /// Reserve `prefix` for `owner`. Registering the same prefix for the same
+    /// owner again is a no-op.
+    fn register(&mut self, prefix: &str, owner: &str) -> PyResult<()> {
+        let key = normalize(prefix);
+        if key.is_empty() {
+            return Err(value_error(format!("Cannot register the empty prefix {:?}", prefix)));
+        }
+        if let Some((registered, current)) = self.entries.get(&key) {
+            if registered == prefix && current == owner {
+                return Ok(());
+            }
+            return Err(value_error(format!(
+                "Prefix {:?} conflicts with {:?}, registered to {:?}",
+                prefix, registered, current
+            )));
+        }
+        self.entries.insert(key, (prefix.to_string(), owner.to_string()));
+        Ok(())
+    }
+
+    fn unregister(&mut self, prefix: &str) -> PyResult<()> {
+        self.check_registered(prefix)?;
+        self.entries.remove(&normalize(prefix));
+        Ok(())
+    }
+
+    /// Owner of exactly `prefix`, or `None`.
+    fn owner(&self, prefix: &str) -> Option<String> {
+        self.entries
+            .get(&normalize(prefix))
+            .filter(|(registered, _)| registered == prefix)
+            .map(|(_, owner)| owner.clone())
+    }
+
+    /// Raise `ValueError` unless exactly `prefix` is registered.
+    fn check(&self, prefix: &str) -> PyResult<()> {
+        self.check_registered(prefix)
+    }
+
+    /// Registered prefixes and their owners.
+    fn prefixes(&self) -> HashMap<String, String> {
+        self.entries.values().cloned().collect()
+    }
+
+    /// New TypeID with a registered `prefix`.
+    fn typeid(&self, py: Python<'_>, prefix: &str) -> PyResult<TypeId> {
+        self.check_registered(prefix)?;
+        crate::typeid::typeid(py, prefix)
+    }
+
+    /// `sortable_key` with a registered `prefix`.
+    #[pyo3(signature = (prefix, reverse=false))]
+    fn sortable_key(&self, py: Python<'_>, prefix: &str, reverse: bool) -> PyResult<String> {
+        self.check_registered(prefix)?;
+        crate::keys::sortable_key(py, Some(prefix), reverse)
+    }
+
+    /// Parse a TypeID string, rejecting prefixes that are not registered.
+    fn parse(&self, s: &str) -> PyResult<TypeId> {
+        let id = TypeId::from_string(s)?;
+        self.check_registered(id.prefix())?;
+        Ok(id)
+    }
+
+    fn __contains__(&self, prefix: &str) -> bool {
+        self.owner(prefix).is_some()
+    }
+
+    fn __len__(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,148 @@
+//! Startup self-test of the environment the generators depend on.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyString};
+
+use crate::derive::{derived_bytes, hkdf, DERIVE_UUID_SALT};
+use crate::expiry::expiring_bytes;
+use crate::monotonic::{next_shared, now_ms};
+use crate::region::{region_bytes, region_code};
+use crate::timestamp::reverse_v7;
+use crate::typeid::TypeId;
+use crate::ulid::{parse_ulid, ulid_bytes, ulid_string};
+
+/// How long the clock checks may sample for.
+const CLOCK_SAMPLE: Duration = Duration::from_millis(5);
+
+fn has_version(bytes: &[u8; 16], version: u8) -> bool {
+    bytes[6] >> 4 == version && bytes[8] >> 6 == 0b10
+}
+
+/// The OS random source works and does not return constant output.
+fn entropy_ok() -> bool {
+    let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+    getrandom::fill(&mut a).is_ok() && getrandom::fill(&mut b).is_ok() && a != b
+}
+
+/// Smallest non-zero step of the wall clock seen while sampling, and whether
+/// it never went backwards during the sample.
+fn sample_clock() -> (Option<Duration>, bool) {
+    let start = Instant::now();
+    let mut last = SystemTime::now();
+    let mut resolution: Option<Duration> = None;
+    let mut monotonic = true;
+    while start.elapsed() < CLOCK_SAMPLE {
+        let now = SystemTime::now();
+        match now.duration_since(last) {
+            Ok(step) if !step.is_zero() => resolution = Some(resolution.map_or(step, |r| r.min(step))),
+            Ok(_) => {}
+            Err(_) => monotonic = false,
+        }
+        last = now;
+    }
+    (resolution, monotonic)
+}
+
+/// The leading 48-bit millisecond field of a v7-style layout.
+fn ms_field(bytes: &[u8; 16]) -> u64 {
+    u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]])
+}
+
+/// Whether `bytes` carries a timestamp within a minute of now.
+fn near_now(bytes: &[u8; 16]) -> bool {
+    ms_field(bytes).abs_diff(now_ms()) < 60_000
+}
+
+/// A sample from each generator, made through the same internal functions
+/// as the Python API (without reporting to the audit hook): version and
+/// variant bits, embedded timestamps and fields, and known answers for the
+/// deterministic kinds.
+fn generator_checks(py: Python<'_>) -> Vec<(&'static str, bool)> {
+    let dns = uuid::Uuid::NAMESPACE_DNS;
+    let python_org = PyString::new(py, "python.org");
+    let name_based = |make: fn(&uuid::Uuid, &[u8]) -> uuid::Uuid, expected: &str| {
+        crate::name_bytes(&python_org).is_ok_and(|name| make(&dns, &name).hyphenated().to_string() == expected)
+    };
+    let time_based = |make: fn(&[u8; 6], uuid::Timestamp) -> [u8; 16], version: u8| {
+        crate::time_node(None, false).is_ok_and(|node| {
+            let (first, second) = (make(&node, crate::gregorian_now(None)), make(&node, crate::gregorian_now(None)));
+            has_version(&first, version) && first != second && first[10..] == node && second[10..] == node
+        })
+    };
+    let mut reversed = *uuid::Uuid::now_v7().as_bytes();
+    reverse_v7(&mut reversed);
+    let (first, second) = (next_shared(), next_shared());
+    // Largest int payload: every payload bit set must survive around the
+    // version and variant fields.
+    let uuid8_int = (((1u128 << 122) - 1).into_pyobject(py).ok())
+        .and_then(|payload| crate::uuid8_from(payload.as_any()).ok())
+        .is_some_and(|id| has_version(&id.bytes, 8) && u128::from_be_bytes(id.bytes).count_ones() == 122 + 2);
+    let uuid8_bytes = crate::uuid8_from(PyBytes::new(py, &[0xff; 16]).as_any())
+        .is_ok_and(|id| has_version(&id.bytes, 8));
+    let ulid = ulid_bytes(false);
+    let typeid = TypeId::new("selfcheck", None).is_ok_and(|id| {
+        has_version(&id.bytes, 7) && TypeId::from_string(&id.__str__()).is_ok_and(|parsed| parsed == id)
+    });
+    let region = region_bytes(0x5a, 8);
+    let expiry = now_ms() + 60_000;
+    let expiring = expiring_bytes(expiry);
+    let hk = hkdf(b"rustid.self_check", DERIVE_UUID_SALT);
+    let derived = derived_bytes(&hk, b"self_check", 0);
+    vec![
+        ("uuid1", time_based(crate::v1_bytes, 1)),
+        ("uuid3", name_based(uuid::Uuid::new_v3, "6fa459ea-ee8a-3ca4-894e-db77e160355e")),
+        ("uuid4", has_version(uuid::Uuid::new_v4().as_bytes(), 4)),
+        ("uuid5", name_based(uuid::Uuid::new_v5, "886313e1-3b8a-5372-9b90-0c9aee199e5d")),
+        ("uuid6", time_based(crate::v6_bytes, 6)),
+        ("uuid7", has_version(uuid::Uuid::now_v7().as_bytes(), 7)),
+        ("uuid7_reverse", has_version(&reversed, 8)),
+        ("uuid7_monotonic", has_version(&first, 7) && has_version(&second, 7) && first < second),
+        ("uuid8", uuid8_int && uuid8_bytes),
+        ("ulid", near_now(&ulid) && parse_ulid(ulid_string(&ulid).as_bytes()) == Ok(ulid)),
+        ("typeid", typeid),
+        ("region_id", has_version(&region, 8) && near_now(&region) && region_code(&region, 8) == 0x5a),
+        ("expiring_id", has_version(&expiring, 8) && ms_field(&expiring) == expiry),
+        (
+            "derive_uuid",
+            has_version(&derived, 4)
+                && derived == derived_bytes(&hk, b"self_check", 0)
+                && derived != derived_bytes(&hk, b"self_check", 1),
+        ),
+    ]
+}
+
+/// Verify entropy, the clock, and a sample from every generator (`uuid1` to
+/// `uuid8`, `ulid`, `typeid`, `region_id`, `expiring_id` and `derive_uuid`),
+/// e.g. from a readiness probe.
+///
+/// Returns `{"ok": bool, "entropy": bool, "clock": {...}, "generators":
+/// {name: bool}}`, where `"clock"` holds the observed `"resolution_ns"`,
+/// whether it stayed `"monotonic"` over a short sample, and its own `"ok"`.
+/// The top-level `"ok"` is true only if every check passed. Takes a few
+/// milliseconds.
+#[pyfunction]
+pub fn self_check(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let entropy = entropy_ok();
+    let (resolution, monotonic) = py.allow_threads(sample_clock);
+    let generators = generator_checks(py);
+    // Time-based IDs need at least millisecond resolution.
+    let clock_ok = monotonic && resolution.is_some_and(|r| r <= Duration::from_millis(1));
+
+    let clock = PyDict::new(py);
+    clock.set_item("ok", clock_ok)?;
+    clock.set_item("resolution_ns", resolution.map(|r| r.as_nanos() as u64))?;
+    clock.set_item("monotonic", monotonic)?;
+    let checks = PyDict::new(py);
+    for (name, ok) in &generators {
+        checks.set_item(name, ok)?;
+    }
+
+    let report = PyDict::new(py);
+    report.set_item("ok", entropy && clock_ok && generators.iter().all(|(_, ok)| *ok))?;
+    report.set_item("entropy", entropy)?;
+    report.set_item("clock", clock)?;
+    report.set_item("generators", checks)?;
+    Ok(report)
+}
@@ -0,0 +1,308 @@
+//! Integer IDs packed as `time | node | sequence`.
+
+use std::time::Duration;
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::audit;
+use crate::monotonic::now_ms;
+use crate::timestamp::unix_ms;
+
+/// Bit allocation of a packed integer ID, most significant field first.
+#[derive(Clone, Copy)]
+pub(crate) struct BitLayout {
+    pub(crate) time_bits: u32,
+    pub(crate) node_bits: u32,
+    pub(crate) seq_bits: u32,
+}
+
+impl BitLayout {
+    /// Twitter's 63-bit layout: 41 bits of milliseconds, 10 of worker, 12 of sequence.
+    pub(crate) const SNOWFLAKE: BitLayout = BitLayout { time_bits: 41, node_bits: 10, seq_bits: 12 };
+
+    pub(crate) fn new(time_bits: u32, node_bits: u32, seq_bits: u32, max_bits: u32) -> PyResult<Self> {
+        if time_bits == 0 || seq_bits == 0 || time_bits + node_bits + seq_bits > max_bits {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Invalid bit layout: time, node and sequence bits must be non-zero and fit in {} bits",
+                max_bits
+            )));
+        }
+        Ok(BitLayout { time_bits, node_bits, seq_bits })
+    }
+
+    /// A preset name (`"snowflake"`: 41/10/12, `"compact"`: 28/12/8) or a
+    /// `(time_bits, node_bits, seq_bits)` tuple.
+    pub(crate) fn from_py(layout: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(name) = layout.extract::<&str>() {
+            return match name {
+                "snowflake" => Ok(BitLayout::SNOWFLAKE),
+                "compact" => BitLayout::new(28, 12, 8, COMPACT_BITS),
+                _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown bit layout: {}", name))),
+            };
+        }
+        let (time_bits, node_bits, seq_bits): (u32, u32, u32) = layout.extract()?;
+        BitLayout::new(time_bits, node_bits, seq_bits, 64)
+    }
+
+    pub(crate) fn pack(&self, tick: u64, node: u64, seq: u64) -> u64 {
+        tick << (self.node_bits + self.seq_bits) | node << self.seq_bits | seq
+    }
+
+    pub(crate) fn unpack(&self, id: u64) -> (u64, u64, u64) {
+        let mask = |bits: u32| (1u64 << bits) - 1;
+        (
+            id >> (self.node_bits + self.seq_bits) & mask(self.time_bits),
+            id >> self.seq_bits & mask(self.node_bits),
+            id & mask(self.seq_bits),
+        )
+    }
+}
+
+/// Clock and sequence state shared by the packed-integer generators.
+pub(crate) struct SequenceGenerator {
+    pub(crate) layout: BitLayout,
+    pub(crate) epoch_ms: u64,
+    pub(crate) unit_ms: u64,
+    pub(crate) node: u64,
+    last_tick: u64,
+    seq: u64,
+}
+
+impl SequenceGenerator {
+    pub(crate) fn new(layout: BitLayout, epoch_ms: u64, unit_ms: u64, node: u64) -> PyResult<Self> {
+        if unit_ms == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Time unit must be at least 1 ms"));
+        }
+        if layout.node_bits < 64 && node >> layout.node_bits != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "Node id {} does not fit in {} bits",
+                node, layout.node_bits
+            )));
+        }
+        Ok(SequenceGenerator { layout, epoch_ms, unit_ms, node, last_tick: 0, seq: 0 })
+    }
+
+    fn current_tick(&self) -> u64 {
+        now_ms().saturating_sub(self.epoch_ms) / self.unit_ms
+    }
+
+    /// Next ID; waits for the next tick, with the GIL released, when the
+    /// sequence is exhausted and holds the last tick if the clock moves
+    /// backwards.
+    pub(crate) fn next(&mut self, py: Python<'_>) -> PyResult<u64> {
+        let tick = self.current_tick().max(self.last_tick);
+        if tick == self.last_tick {
+            self.seq += 1;
+            if self.seq >> self.layout.seq_bits != 0 {
+                let next = py.allow_threads(|| {
+                    let mut next = self.current_tick();
+                    while next <= self.last_tick {
+                        std::thread::sleep(Duration::from_micros(100));
+                        next = self.current_tick();
+                    }
+                    next
+                });
+                self.last_tick = next;
+                self.seq = 0;
+            }
+        } else {
+            self.last_tick = tick;
+            self.seq = 0;
+        }
+        if self.last_tick >> self.layout.time_bits != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyOverflowError, _>(
+                "Timestamp no longer fits in the configured time bits",
+            ));
+        }
+        Ok(self.layout.pack(self.last_tick, self.node, self.seq))
+    }
+}
+
+/// 48-bit `time | device | counter` IDs for constrained telemetry payloads.
+///
+/// Time is counted in `unit_ms` ticks since `epoch_ms`; the defaults give
+/// one-second ticks for ~8.5 years, 4096 devices, and 256 IDs per second
+/// per device. Packed output is 6 big-endian bytes per ID.
+#[pyclass]
+pub struct CompactIdGenerator {
+    inner: SequenceGenerator,
+}
+
+const COMPACT_BITS: u32 = 48;
+
+#[pymethods]
+impl CompactIdGenerator {
+    #[new]
+    #[pyo3(signature = (device_id, time_bits=28, device_bits=12, counter_bits=8, epoch_ms=1_704_067_200_000, unit_ms=1000))]
+    fn new(device_id: u64, time_bits: u32, device_bits: u32, counter_bits: u32, epoch_ms: u64, unit_ms: u64) -> PyResult<Self> {
+        let layout = BitLayout::new(time_bits, device_bits, counter_bits, COMPACT_BITS)?;
+        Ok(CompactIdGenerator { inner: SequenceGenerator::new(layout, epoch_ms, unit_ms, device_id)? })
+    }
+
+    fn next_id(&mut self, py: Python<'_>) -> PyResult<u64> {
+        let id = self.inner.next(py)?;
+        audit::record(py, "compact_id", [id])?;
+        Ok(id)
+    }
+
+    fn next_bytes<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let id = self.inner.next(py)?;
+        audit::record(py, "compact_id", [id])?;
+        Ok(PyBytes::new(py, &id.to_be_bytes()[2..]))
+    }
+
+    fn next_batch(&mut self, py: Python<'_>, count: usize) -> PyResult<Vec<u64>> {
+        let ids = (0..count).map(|_| self.inner.next(py)).collect::<PyResult<Vec<u64>>>()?;
+        audit::record(py, "compact_id", &ids)?;
+        Ok(ids)
+    }
+
+    /// `count` IDs packed back to back, 6 bytes each.
+    fn next_packed<'py>(&mut self, py: Python<'py>, count: usize) -> PyResult<Bound<'py, PyBytes>> {
+        let ids = (0..count).map(|_| self.inner.next(py)).collect::<PyResult<Vec<u64>>>()?;
+        audit::record(py, "compact_id", &ids)?;
+        let mut out = Vec::with_capacity(count * 6);
+        for id in ids {
+            out.extend_from_slice(&id.to_be_bytes()[2..]);
+        }
+        Ok(PyBytes::new(py, &out))
+    }
+
+    /// Split an ID into `(timestamp_ms, device_id, counter)`.
+    fn decode(&self, id: u64) -> (u64, u64, u64) {
+        let (tick, device, counter) = self.inner.layout.unpack(id);
+        (self.inner.epoch_ms + tick * self.inner.unit_ms, device, counter)
+    }
+
+    #[getter]
+    fn device_id(&self) -> u64 {
+        self.inner.node
+    }
+}
+
+/// Twitter's Snowflake epoch, 2010-11-04T01:42:54.657Z.
+const TWITTER_EPOCH_MS: u64 = 1_288_834_974_657;
+
+/// Snowflake 64-bit ID generator: milliseconds since `epoch`, then
+/// `datacenter_id` and `worker_id`, then a per-millisecond sequence.
+///
+/// The default layout is Twitter's (41 time bits, 5 datacenter bits, 5 worker
+/// bits, 12 sequence bits); `layout` accepts the same presets and
+/// `(time_bits, node_bits, seq_bits)` tuples as `find_gaps`, with the node
+/// bits split into `datacenter_bits` and the rest for the worker. When the
+/// sequence rolls over the generator waits for the next millisecond, and if
+/// the clock moves backwards it keeps issuing IDs from the last millisecond
+/// seen, so IDs from one instance always increase.
+#[pyclass]
+pub struct Snowflake {
+    inner: SequenceGenerator,
+    datacenter_bits: u32,
+}
+
+#[pymethods]
+impl Snowflake {
+    #[new]
+    #[pyo3(signature = (worker_id=0, datacenter_id=0, epoch=None, layout=None, datacenter_bits=5))]
+    fn new(
+        worker_id: u64,
+        datacenter_id: u64,
+        epoch: Option<&Bound<'_, PyAny>>,
+        layout: Option<&Bound<'_, PyAny>>,
+        datacenter_bits: u32,
+    ) -> PyResult<Self> {
+        let layout = match layout {
+            Some(layout) => BitLayout::from_py(layout)?,
+            None => BitLayout::SNOWFLAKE,
+        };
+        if datacenter_bits > layout.node_bits {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "datacenter_bits must be at most the layout's {} node bits",
+                layout.node_bits
+            )));
+        }
+        let worker_bits = layout.node_bits - datacenter_bits;
+        if worker_id >> worker_bits != 0 || datacenter_id >> datacenter_bits != 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "worker_id must fit in {} bits and datacenter_id in {} bits",
+                worker_bits, datacenter_bits
+            )));
+        }
+        let epoch_ms = match epoch {
+            Some(epoch) => unix_ms(epoch)?,
+            None => TWITTER_EPOCH_MS,
+        };
+        let node = datacenter_id << worker_bits | worker_id;
+        Ok(Snowflake { inner: SequenceGenerator::new(layout, epoch_ms, 1, node)?, datacenter_bits })
+    }
+
+    fn next_id(&mut self, py: Python<'_>) -> PyResult<u64> {
+        let id = self.inner.next(py)?;
+        audit::record(py, "snowflake", [id])?;
+        Ok(id)
+    }
+
+    /// `n` consecutive IDs, in increasing order.
+    fn next_batch(&mut self, py: Python<'_>, n: usize) -> PyResult<Vec<u64>> {
+        let ids = (0..n).map(|_| self.inner.next(py)).collect::<PyResult<Vec<u64>>>()?;
+        audit::record(py, "snowflake", &ids)?;
+        Ok(ids)
+    }
+
+    /// Split an ID into `(timestamp_ms, datacenter_id, worker_id, sequence)`.
+    fn decode(&self, id: u64) -> (u64, u64, u64, u64) {
+        let (tick, node, seq) = self.inner.layout.unpack(id);
+        let worker_bits = self.inner.layout.node_bits - self.datacenter_bits;
+        (self.inner.epoch_ms + tick, node >> worker_bits, node & ((1 << worker_bits) - 1), seq)
+    }
+
+    #[getter]
+    fn worker_id(&self) -> u64 {
+        self.inner.node & ((1 << (self.inner.layout.node_bits - self.datacenter_bits)) - 1)
+    }
+
+    #[getter]
+    fn datacenter_id(&self) -> u64 {
+        self.inner.node >> (self.inner.layout.node_bits - self.datacenter_bits)
+    }
+
+    #[getter]
+    fn epoch_ms(&self) -> u64 {
+        self.inner.epoch_ms
+    }
+}
+
+/// Missing sequence numbers in sorted packed IDs, as inclusive
+/// `(first_missing_id, last_missing_id)` ranges.
+///
+/// IDs are grouped by time tick and node; within each group sequence numbers
+/// are expected to run contiguously from 0, so both holes and a late first
+/// sequence number are reported. Missing IDs after the last one seen in a
+/// group cannot be detected. `layout` defaults to `"snowflake"`.
+#[pyfunction]
+#[pyo3(signature = (sorted_ids, layout=None))]
+pub fn find_gaps(py: Python<'_>, sorted_ids: Vec<u64>, layout: Option<&Bound<'_, PyAny>>) -> PyResult<Vec<(u64, u64)>> {
+    let layout = match layout {
+        Some(layout) => BitLayout::from_py(layout)?,
+        None => BitLayout::SNOWFLAKE,
+    };
+    if let Some(i) = sorted_ids.windows(2).position(|pair| pair[0] > pair[1]) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("IDs are not sorted at index {}", i + 1)));
+    }
+    Ok(py.allow_threads(|| {
+        let mut gaps = Vec::new();
+        let mut previous: Option<(u64, u64, u64)> = None;
+        for &id in &sorted_ids {
+            let (tick, node, seq) = layout.unpack(id);
+            let expected = match previous {
+                Some((t, n, s)) if t == tick && n == node => s + 1,
+                _ => 0,
+            };
+            if seq > expected {
+                gaps.push((layout.pack(tick, node, expected), layout.pack(tick, node, seq - 1)));
+            }
+            previous = Some((tick, node, seq.max(expected.saturating_sub(1))));
+        }
+        gaps
+    }))
+}
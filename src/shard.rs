@@ -0,0 +1,98 @@
+//! Write-sharding of time-based IDs by creation-time window.
+
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use crate::timestamp::{no_timestamp, unix_ms_of};
+use crate::ulid::{parse_ulid, Ulid};
+
+const DEFAULT_WINDOW_MS: u64 = 3_600_000;
+
+fn value_error(msg: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(msg)
+}
+
+/// Milliseconds in a window like `"500ms"`, `"30s"`, `"15m"`, `"1h"`, `"1d"`
+/// or `"1w"`.
+fn parse_window(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = s.split_at(split);
+    let unit_ms = match unit {
+        "ms" => 1,
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        "w" => 604_800_000,
+        _ => return None,
+    };
+    count.parse::<u64>().ok()?.checked_mul(unit_ms)
+}
+
+/// Milliseconds in a duration string, an int of milliseconds, or a
+/// `timedelta`; `what` names the argument in errors.
+pub(crate) fn duration_ms(value: &Bound<'_, PyAny>, what: &str) -> PyResult<u64> {
+    let ms = if let Ok(s) = value.downcast::<PyString>() {
+        let s = s.to_str()?;
+        parse_window(s).ok_or_else(|| value_error(format!("Invalid {}: {:?}", what, s)))?
+    } else if let Ok(ms) = value.extract::<u64>() {
+        ms
+    } else {
+        let seconds: f64 = value.call_method0("total_seconds")?.extract()?;
+        (seconds * 1000.0) as u64
+    };
+    if ms == 0 {
+        return Err(value_error(format!("{} must be at least 1 ms", what)));
+    }
+    Ok(ms)
+}
+
+/// Window length, by default one hour.
+fn window_ms(window: Option<&Bound<'_, PyAny>>) -> PyResult<u64> {
+    window.map_or(Ok(DEFAULT_WINDOW_MS), |w| duration_ms(w, "window"))
+}
+
+/// Creation time in Unix ms of a v1/v6/v7 UUID or a ULID (object or string).
+fn creation_ms(id: &Bound<'_, PyAny>) -> PyResult<u64> {
+    let ulid_bytes = if let Ok(ulid) = id.downcast::<Ulid>() {
+        Some(ulid.get().bytes)
+    } else if let Ok(s) = id.extract::<&str>() {
+        parse_ulid(s.as_bytes()).ok()
+    } else {
+        None
+    };
+    if let Some(bytes) = ulid_bytes {
+        return Ok(u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]));
+    }
+    let bytes = crate::uuid_bytes(id)?;
+    unix_ms_of(&bytes).ok_or_else(|| no_timestamp(&bytes))
+}
+
+/// Shard for `id` when consecutive time windows are assigned to `shards`
+/// round-robin: window `n` (counted from the Unix epoch, in UTC) goes to
+/// shard `n % shards`.
+///
+/// All IDs created in one window land on the same shard, and writes move on
+/// to the next shard when the window rolls over. `id` is a v7 (or v1/v6)
+/// UUID or a ULID; `window` is a string such as `"15m"`, `"1h"` or `"1d"`
+/// (units `ms`, `s`, `m`, `h`, `d`, `w`), an int of milliseconds, or a
+/// `timedelta`.
+#[pyfunction]
+#[pyo3(signature = (id, shards, window=None))]
+pub fn route_by_time(id: &Bound<'_, PyAny>, shards: u64, window: Option<&Bound<'_, PyAny>>) -> PyResult<u64> {
+    if shards == 0 {
+        return Err(value_error("shards must be at least 1".to_string()));
+    }
+    Ok(creation_ms(id)? / window_ms(window)? % shards)
+}
+
+/// The window `id` was created in, as `(start_ms, end_ms)` in Unix
+/// milliseconds with `end_ms` exclusive, using the windows of
+/// `route_by_time`.
+#[pyfunction]
+#[pyo3(signature = (id, window=None))]
+pub fn time_window(id: &Bound<'_, PyAny>, window: Option<&Bound<'_, PyAny>>) -> PyResult<(u64, u64)> {
+    let window = window_ms(window)?;
+    let start = creation_ms(id)? / window * window;
+    Ok((start, start.saturating_add(window)))
+}
@@ -0,0 +1,284 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::audit;
+use crate::monotonic::{Overflow, Uuid7Counter};
+use crate::ulid::{Ulid, UlidCounter};
+use crate::UUID;
+
+const DEFAULT_CHUNK: usize = 1024;
+
+/// Rust-side generator of 16-byte IDs behind the streaming APIs.
+pub(crate) enum IdSource {
+    Uuid4,
+    Uuid7(Uuid7Counter),
+}
+
+impl IdSource {
+    pub(crate) fn new(kind: &str) -> PyResult<Self> {
+        match kind {
+            "uuid4" => Ok(IdSource::Uuid4),
+            "uuid7" => Ok(IdSource::Uuid7(Uuid7Counter::new())),
+            _ => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported stream kind: {}", kind))),
+        }
+    }
+
+    /// Continue after `last`; random kinds have no state to restore.
+    fn resume(&mut self, last: &[u8; 16]) {
+        match self {
+            IdSource::Uuid4 => {}
+            IdSource::Uuid7(generator) => *generator = Uuid7Counter::resume(last),
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> [u8; 16] {
+        match self {
+            IdSource::Uuid4 => *uuid::Uuid::new_v4().as_bytes(),
+            IdSource::Uuid7(generator) => generator.next(),
+        }
+    }
+
+    fn next_with(&mut self, overflow: Overflow) -> PyResult<[u8; 16]> {
+        match self {
+            IdSource::Uuid4 => Ok(*uuid::Uuid::new_v4().as_bytes()),
+            IdSource::Uuid7(generator) => generator.next_with(overflow),
+        }
+    }
+}
+
+/// Generator behind an `IdStream`: 16-byte IDs, monotonic ULIDs or nano IDs
+/// of `size` symbols.
+enum StreamSource {
+    Ids(IdSource),
+    Ulid(UlidCounter),
+    NanoId(usize),
+}
+
+/// One buffered `IdStream` value.
+enum Item {
+    Id([u8; 16]),
+    Ulid([u8; 16]),
+    Text(String),
+}
+
+impl Item {
+    fn into_py(self, py: Python<'_>) -> PyResult<PyObject> {
+        match self {
+            Item::Id(bytes) => Ok(Py::new(py, UUID { bytes })?.into_any()),
+            Item::Ulid(bytes) => Ok(Py::new(py, Ulid { bytes })?.into_any()),
+            Item::Text(text) => Ok(text.into_pyobject(py)?.into_any().unbind()),
+        }
+    }
+}
+
+/// Lazily generated stream of IDs refilled in Rust-side chunks, so that
+/// jobs producing millions of rows never hold more than one chunk.
+///
+/// `kind` is `"uuid7"` (monotonic), `"ulid"` (monotonic `ULID`s), `"uuid4"`
+/// or `"nano_id"` (secure, `size` symbols of the URL-safe alphabet, 21 by
+/// default). For `"uuid7"` and `"ulid"`, `overflow` picks what happens when
+/// more IDs are needed within one millisecond than the counter holds: `"borrow"` (the default) moves on to
+/// the next millisecond, `"wait"` sleeps until the clock gets there, and
+/// `"raise"` raises `OverflowError`.
+#[pyclass]
+pub struct IdStream {
+    kind: String,
+    source: StreamSource,
+    overflow: Overflow,
+    buffer: std::vec::IntoIter<Item>,
+    chunk_size: usize,
+    last: Option<String>,
+    count: u64,
+}
+
+impl IdStream {
+    fn build(kind: &str, chunk_size: Option<usize>, overflow: Overflow, size: Option<usize>) -> PyResult<Self> {
+        let source = match kind {
+            "nano_id" => StreamSource::NanoId(size.unwrap_or(21)),
+            "ulid" if size.is_none() => StreamSource::Ulid(UlidCounter::default()),
+            _ if size.is_some() => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("size only applies to nano_id streams"))
+            }
+            _ => StreamSource::Ids(IdSource::new(kind)?),
+        };
+        Ok(IdStream {
+            kind: kind.to_string(),
+            source,
+            overflow,
+            buffer: Vec::new().into_iter(),
+            chunk_size: chunk_size.unwrap_or(DEFAULT_CHUNK).max(1),
+            last: None,
+            count: 0,
+        })
+    }
+
+    /// Generate the next chunk. An exhausted counter under `"raise"` ends the
+    /// chunk early, and only raises if no ID could be generated at all.
+    fn refill(&mut self) -> PyResult<()> {
+        let mut buffer = Vec::with_capacity(self.chunk_size);
+        match &mut self.source {
+            StreamSource::NanoId(size) => {
+                let symbols = crate::alphabets::url_safe_symbols();
+                buffer.extend((0..self.chunk_size).map(|_| Item::Text(crate::nano_id_string(*size, symbols, true))));
+            }
+            StreamSource::Ids(source) => {
+                while buffer.len() < self.chunk_size {
+                    match source.next_with(self.overflow) {
+                        Ok(id) => buffer.push(Item::Id(id)),
+                        Err(err) if buffer.is_empty() => return Err(err),
+                        Err(_) => break,
+                    }
+                }
+            }
+            StreamSource::Ulid(counter) => {
+                while buffer.len() < self.chunk_size {
+                    match counter.next_with(self.overflow) {
+                        Ok(id) => buffer.push(Item::Ulid(id)),
+                        Err(err) if buffer.is_empty() => return Err(err),
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+        self.buffer = buffer.into_iter();
+        Ok(())
+    }
+
+    /// Next value, refilling without the GIL so that `overflow="wait"` does
+    /// not stall other threads.
+    fn next_item(&mut self, py: Python<'_>) -> PyResult<Item> {
+        let item = match self.buffer.next() {
+            Some(item) => item,
+            None => {
+                py.allow_threads(|| self.refill())?;
+                self.buffer.next().expect("refill yields at least one item")
+            }
+        };
+        self.last = Some(match &item {
+            Item::Id(bytes) | Item::Ulid(bytes) => hex::encode(bytes),
+            Item::Text(text) => text.clone(),
+        });
+        self.count += 1;
+        Ok(item)
+    }
+
+    fn record(&self, py: Python<'_>, items: &[Item]) -> PyResult<()> {
+        match self.source {
+            StreamSource::Ids(_) => audit::record(
+                py,
+                &self.kind,
+                items.iter().filter_map(|item| match item {
+                    Item::Id(bytes) => Some(UUID { bytes: *bytes }),
+                    _ => None,
+                }),
+            ),
+            StreamSource::Ulid(_) => audit::record(
+                py,
+                &self.kind,
+                items.iter().filter_map(|item| match item {
+                    Item::Ulid(bytes) => Some(Ulid { bytes: *bytes }),
+                    _ => None,
+                }),
+            ),
+            StreamSource::NanoId(_) => audit::record(
+                py,
+                &self.kind,
+                items.iter().filter_map(|item| match item {
+                    Item::Text(text) => Some(text),
+                    _ => None,
+                }),
+            ),
+        }
+    }
+}
+
+#[pymethods]
+impl IdStream {
+    #[new]
+    #[pyo3(signature = (kind="uuid7", chunk_size=None, overflow="borrow", size=None))]
+    fn new(kind: &str, chunk_size: Option<usize>, overflow: &str, size: Option<usize>) -> PyResult<Self> {
+        IdStream::build(kind, chunk_size, Overflow::parse(overflow)?, size)
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        let item = self.next_item(py)?;
+        self.record(py, std::slice::from_ref(&item))?;
+        item.into_py(py)
+    }
+
+    /// The next `n` values as a list, continuing the same stream.
+    fn take(&mut self, py: Python<'_>, n: usize) -> PyResult<Vec<PyObject>> {
+        let items = (0..n).map(|_| self.next_item(py)).collect::<PyResult<Vec<_>>>()?;
+        self.record(py, &items)?;
+        items.into_iter().map(|item| item.into_py(py)).collect()
+    }
+
+    #[getter]
+    fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Checkpoint of the last ID handed out; pass it to `IdStream.resume`.
+    fn state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let state = PyDict::new(py);
+        state.set_item("kind", &self.kind)?;
+        state.set_item("last", &self.last)?;
+        state.set_item("count", self.count)?;
+        state.set_item("overflow", self.overflow.name())?;
+        if let StreamSource::NanoId(size) = self.source {
+            state.set_item("size", size)?;
+        }
+        Ok(state)
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (state, chunk_size=None))]
+    fn resume(state: &Bound<'_, PyDict>, chunk_size: Option<usize>) -> PyResult<Self> {
+        let invalid = |msg: &str| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid stream state: {}", msg));
+        let kind: String = state
+            .get_item("kind")?
+            .ok_or_else(|| invalid("missing kind"))?
+            .extract()?;
+        let last: Option<String> = match state.get_item("last")? {
+            Some(value) => value.extract()?,
+            None => None,
+        };
+        let count: u64 = match state.get_item("count")? {
+            Some(value) => value.extract()?,
+            None => 0,
+        };
+
+        let overflow = match state.get_item("overflow")? {
+            Some(value) => Overflow::parse(value.extract()?)?,
+            None => Overflow::default(),
+        };
+
+        let size: Option<usize> = match state.get_item("size")? {
+            Some(value) => value.extract()?,
+            None => None,
+        };
+
+        let mut stream = IdStream::build(&kind, chunk_size, overflow, size)?;
+        if let Some(last) = &last {
+            let mut bytes = [0u8; 16];
+            match &mut stream.source {
+                StreamSource::Ids(source) => {
+                    hex::decode_to_slice(last, &mut bytes).map_err(|_| invalid("bad last id"))?;
+                    source.resume(&bytes);
+                }
+                StreamSource::Ulid(counter) => {
+                    hex::decode_to_slice(last, &mut bytes).map_err(|_| invalid("bad last id"))?;
+                    *counter = UlidCounter::resume(&bytes);
+                }
+                StreamSource::NanoId(_) => {}
+            }
+        }
+        stream.last = last;
+        stream.count = count;
+        Ok(stream)
+    }
+}
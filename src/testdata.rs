@@ -0,0 +1,196 @@
+//! Synthetic ID data for load tests and benchmarks.
+
+use std::collections::HashSet;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+
+use crate::audit;
+use crate::columns::ColumnKind;
+use crate::monotonic::Uuid7Counter;
+use crate::timestamp::unix_ms;
+use crate::UUID;
+
+/// `count` v7 IDs with timestamps spread over `[start, end)`, in ascending order.
+///
+/// `start` and `end` are Unix milliseconds or `datetime`s. `"uniform"` spaces
+/// the IDs evenly (constant rate); `"poisson"` places them as random
+/// arrivals of a Poisson process, so gaps and bursts look like real traffic.
+#[pyfunction]
+#[pyo3(signature = (count, start, end, distribution="uniform"))]
+pub fn uuid7_backfill_batch(
+    py: Python<'_>,
+    count: usize,
+    start: &Bound<'_, PyAny>,
+    end: &Bound<'_, PyAny>,
+    distribution: &str,
+) -> PyResult<Vec<UUID>> {
+    let (start, end) = (unix_ms(start)?, unix_ms(end)?);
+    if end <= start {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("end must be after start"));
+    }
+    let poisson = match distribution {
+        "uniform" => false,
+        "poisson" => true,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported distribution: {}", distribution))),
+    };
+    let span = (end - start) as f64;
+    let offsets: Vec<f64> = py.allow_threads(|| {
+        if poisson {
+            // Arrival times of a Poisson process conditioned on `count` events
+            // in the window: cumulative exponential gaps, normalized by one more gap.
+            let mut total = 0.0;
+            let mut arrivals: Vec<f64> = (0..=count)
+                .map(|_| {
+                    total += -(1.0 - fastrand::f64()).ln();
+                    total
+                })
+                .collect();
+            arrivals.pop();
+            arrivals.into_iter().map(|t| t / total).collect()
+        } else {
+            (0..count).map(|i| i as f64 / count as f64).collect()
+        }
+    });
+    let mut counter = Uuid7Counter::new();
+    let ids: Vec<UUID> = offsets
+        .into_iter()
+        .map(|offset| UUID { bytes: counter.next_at(start + (offset * span) as u64) })
+        .collect();
+    audit::record(py, "uuid7", ids.iter().copied())?;
+    Ok(ids)
+}
+
+/// Cumulative Zipf weights `1/k^s` for ranks `1..=n`, normalized to end at 1.
+fn zipf_cdf(n: usize, s: f64) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut cdf: Vec<f64> = (1..=n)
+        .map(|k| {
+            total += (k as f64).powf(-s);
+            total
+        })
+        .collect();
+    for p in &mut cdf {
+        *p /= total;
+    }
+    cdf
+}
+
+/// Draw `count` keys from `population` with a skewed access pattern.
+///
+/// `population` is a sequence of keys (the first ones are the hottest) or an
+/// int `n`, in which case ranks `0..n` are returned. `"zipf"` picks rank `k`
+/// with probability proportional to `1/(k+1)^s`; `"uniform"` ignores `s`.
+#[pyfunction]
+#[pyo3(signature = (population, count, distribution="zipf", s=1.2))]
+pub fn sample_keys(
+    py: Python<'_>,
+    population: &Bound<'_, PyAny>,
+    count: usize,
+    distribution: &str,
+    s: f64,
+) -> PyResult<PyObject> {
+    let keys: Option<Vec<Bound<'_, PyAny>>> = match population.extract::<usize>() {
+        Ok(_) => None,
+        Err(_) => Some(population.try_iter()?.collect::<PyResult<_>>()?),
+    };
+    let n = match &keys {
+        Some(keys) => keys.len(),
+        None => population.extract::<usize>()?,
+    };
+    if n == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Population is empty"));
+    }
+    let zipf = match distribution {
+        "zipf" if s.is_finite() && s >= 0.0 => true,
+        "zipf" => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("s must be a non-negative number")),
+        "uniform" => false,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported distribution: {}", distribution))),
+    };
+    let ranks: Vec<usize> = py.allow_threads(|| {
+        if zipf {
+            let cdf = zipf_cdf(n, s);
+            (0..count)
+                .map(|_| {
+                    let u = fastrand::f64();
+                    cdf.partition_point(|&p| p < u).min(n - 1)
+                })
+                .collect()
+        } else {
+            (0..count).map(|_| fastrand::usize(0..n)).collect()
+        }
+    });
+    match keys {
+        Some(keys) => Ok(ranks.into_iter().map(|k| keys[k].clone()).collect::<Vec<_>>().into_pyobject(py)?.into_any().unbind()),
+        None => Ok(ranks.into_pyobject(py)?.into_any().unbind()),
+    }
+}
+
+/// Collision window in ms (`None` for the whole run) and random bits per ID.
+/// Time-prefixed kinds can only collide with IDs from the same millisecond.
+fn collision_model(kind: &ColumnKind) -> (Option<f64>, u32) {
+    match kind {
+        ColumnKind::Uuid4 => (None, 122),
+        ColumnKind::Uuid7 | ColumnKind::Prefixed(_) => (Some(1.0), 74),
+        // 12 bytes of a v7: 48 timestamp bits, then 4 version and 2 variant bits.
+        ColumnKind::ShortId => (Some(1.0), 42),
+        ColumnKind::NanoId(size) => (None, (*size as u32).saturating_mul(6)),
+    }
+}
+
+/// Monte-Carlo estimate of duplicate IDs for `kind` at a steady traffic rate.
+///
+/// `kind` is a column spec such as `"short_id"` or `"nanoid:10"`. Each trial
+/// draws `rate_per_sec * duration` random values from the kind's random space
+/// (per millisecond for time-prefixed kinds) and counts repeats. Returns a
+/// dict with `ids`, per-trial `collisions`, their `mean`, and the birthday
+/// approximation `expected`. Runtime grows with `ids * trials`.
+#[pyfunction]
+#[pyo3(signature = (kind, rate_per_sec, duration, trials=1))]
+pub fn simulate_collisions<'py>(
+    py: Python<'py>,
+    kind: &str,
+    rate_per_sec: f64,
+    duration: f64,
+    trials: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let kind = ColumnKind::parse(kind)?;
+    if !(rate_per_sec > 0.0 && duration > 0.0 && rate_per_sec.is_finite() && duration.is_finite()) || trials == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "rate_per_sec and duration must be positive and trials at least 1",
+        ));
+    }
+    let (window_ms, bits) = collision_model(&kind);
+    let space = if bits >= 128 { u128::MAX } else { 1u128 << bits };
+    let (windows, per_window) = match window_ms {
+        Some(ms) => ((duration * 1000.0 / ms).ceil() as u64, rate_per_sec * ms / 1000.0),
+        None => (1, rate_per_sec * duration),
+    };
+    let collisions: Vec<u64> = py.allow_threads(|| {
+        (0..trials)
+            .into_par_iter()
+            .map(|_| {
+                let mut seen = HashSet::new();
+                let mut collisions = 0;
+                for _ in 0..windows {
+                    // Round the fractional part at random so the average rate is exact.
+                    let count = per_window.floor() as u64 + (fastrand::f64() < per_window.fract()) as u64;
+                    seen.clear();
+                    for _ in 0..count {
+                        if !seen.insert(fastrand::u128(0..space)) {
+                            collisions += 1;
+                        }
+                    }
+                }
+                collisions
+            })
+            .collect()
+    });
+    let result = PyDict::new(py);
+    result.set_item("ids", (rate_per_sec * duration).round() as u64)?;
+    result.set_item("mean", collisions.iter().sum::<u64>() as f64 / trials as f64)?;
+    result.set_item("expected", windows as f64 * per_window * per_window / (2.0 * space as f64))?;
+    result.set_item("collisions", collisions)?;
+    Ok(result)
+}
@@ -0,0 +1,120 @@
+//! Timestamps embedded in time-based UUIDs.
+
+use pyo3::prelude::*;
+
+/// 100ns intervals between the Gregorian epoch (1582-10-15) and the Unix epoch.
+pub(crate) const GREGORIAN_OFFSET: u64 = 0x01B2_1DD2_1381_4000;
+
+/// 60-bit count of 100ns intervals since the Gregorian epoch, for v1 and v6.
+pub(crate) fn gregorian_ticks(bytes: &[u8; 16]) -> Option<u64> {
+    let time_hi = u16::from_be_bytes([bytes[6] & 0x0f, bytes[7]]) as u64;
+    match bytes[6] >> 4 {
+        1 => {
+            let time_low = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+            let time_mid = u16::from_be_bytes([bytes[4], bytes[5]]) as u64;
+            Some(time_hi << 48 | time_mid << 32 | time_low)
+        }
+        6 => {
+            let high = u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]);
+            Some(high << 12 | time_hi)
+        }
+        _ => None,
+    }
+}
+
+/// Unix timestamp in 100ns units for v1/v6 IDs (saturating before 1970).
+pub(crate) fn unix_ticks(bytes: &[u8; 16]) -> Option<u64> {
+    gregorian_ticks(bytes).map(|ticks| ticks.saturating_sub(GREGORIAN_OFFSET))
+}
+
+/// Creation time in Unix milliseconds for time-based (v1, v6, v7) IDs.
+pub(crate) fn unix_ms_of(bytes: &[u8; 16]) -> Option<u64> {
+    if bytes[6] >> 4 == 7 {
+        return Some(u64::from_be_bytes([0, 0, bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]]));
+    }
+    unix_ticks(bytes).map(|ticks| ticks / 10_000)
+}
+
+/// Creation time in Unix seconds for time-based IDs, keeping the 100ns
+/// resolution of v1 and v6.
+pub(crate) fn unix_seconds_of(bytes: &[u8; 16]) -> Option<f64> {
+    match unix_ticks(bytes) {
+        Some(ticks) => Some(ticks as f64 / 1e7),
+        None => unix_ms_of(bytes).map(|ms| ms as f64 / 1000.0),
+    }
+}
+
+/// `ValueError` for reading the creation time of a non-time-based ID.
+pub(crate) fn no_timestamp(bytes: &[u8; 16]) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+        "UUID version {} has no timestamp; only versions 1, 6 and 7 do",
+        bytes[6] >> 4
+    ))
+}
+
+/// Invert the v7 timestamp and `rand_a` bits in place so byte order becomes
+/// newest-first. Inverting the version nibble too turns 7 into 8, so the
+/// result is a v8 (custom layout) UUID that is not read as a creation time;
+/// applying it twice restores the original v7.
+pub(crate) fn reverse_v7(bytes: &mut [u8; 16]) {
+    for byte in &mut bytes[0..8] {
+        *byte = !*byte;
+    }
+}
+
+/// Unix milliseconds from an int or an object with `.timestamp()`, such as `datetime`.
+pub(crate) fn unix_ms(obj: &Bound<'_, PyAny>) -> PyResult<u64> {
+    if let Ok(ms) = obj.extract::<u64>() {
+        return Ok(ms);
+    }
+    let seconds: f64 = obj.call_method0("timestamp")?.extract()?;
+    if seconds < 0.0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Timestamps before 1970 are not supported"));
+    }
+    Ok((seconds * 1000.0) as u64)
+}
+
+/// Clock problems in a sequence of time-based IDs, as `(index, reason)` pairs.
+///
+/// `reason` is `"future"` for timestamps more than `tolerance_ms` after
+/// `now` (default: the current time), `"before_launch"` for timestamps
+/// before `launched_at`, and `"out_of_sequence"` for timestamps more than
+/// `max_skew_ms` earlier than the last unflagged ID before them. `now` and
+/// `launched_at` are Unix milliseconds or `datetime`s. IDs that are not v1,
+/// v6 or v7 are skipped.
+#[pyfunction]
+#[pyo3(signature = (ids, now=None, launched_at=None, tolerance_ms=60_000, max_skew_ms=60_000))]
+pub fn detect_anomalies(
+    py: Python<'_>,
+    ids: &Bound<'_, PyAny>,
+    now: Option<&Bound<'_, PyAny>>,
+    launched_at: Option<&Bound<'_, PyAny>>,
+    tolerance_ms: u64,
+    max_skew_ms: u64,
+) -> PyResult<Vec<(usize, &'static str)>> {
+    let ids = crate::array::batch_bytes(ids)?;
+    let now = match now {
+        Some(now) => unix_ms(now)?,
+        None => crate::monotonic::now_ms(),
+    };
+    let max_ms = now.saturating_add(tolerance_ms);
+    let min_ms = launched_at.map(unix_ms).transpose()?.unwrap_or(0);
+    Ok(py.allow_threads(|| {
+        let mut anomalies = Vec::new();
+        let mut last_ok: Option<u64> = None;
+        for (i, ms) in ids.iter().enumerate().filter_map(|(i, id)| unix_ms_of(id).map(|ms| (i, ms))) {
+            let reason = if ms > max_ms {
+                "future"
+            } else if ms < min_ms {
+                "before_launch"
+            } else if last_ok.is_some_and(|last| ms.saturating_add(max_skew_ms) < last) {
+                "out_of_sequence"
+            } else {
+                last_ok = Some(ms);
+                continue;
+            };
+            anomalies.push((i, reason));
+        }
+        anomalies
+    }))
+}
@@ -0,0 +1,83 @@
+//! W3C Trace Context identifiers (`traceparent` trace-id and parent-id).
+
+use pyo3::prelude::*;
+use rayon::prelude::*;
+
+use crate::audit;
+
+/// 16 bytes from the OS random source as 32 lowercase hex digits; never all
+/// zeros, which the spec reserves as invalid. The spec asks for IDs that are
+/// hard to guess, so the non-cryptographic `fastrand` is not used here.
+fn new_trace_id() -> String {
+    let value = loop {
+        let mut buf = [0u8; 16];
+        getrandom::fill(&mut buf).expect("OS random source is available");
+        let value = u128::from_be_bytes(buf);
+        if value != 0 {
+            break value;
+        }
+    };
+    format!("{:032x}", value)
+}
+
+/// 8 bytes from the OS random source as 16 lowercase hex digits, never all
+/// zeros.
+fn new_span_id() -> String {
+    let value = loop {
+        let value = getrandom::u64().expect("OS random source is available");
+        if value != 0 {
+            break value;
+        }
+    };
+    format!("{:016x}", value)
+}
+
+#[pyfunction]
+pub fn trace_id(py: Python<'_>) -> PyResult<String> {
+    let id = new_trace_id();
+    audit::record(py, "trace_id", [&id])?;
+    Ok(id)
+}
+
+#[pyfunction]
+pub fn span_id(py: Python<'_>) -> PyResult<String> {
+    let id = new_span_id();
+    audit::record(py, "span_id", [&id])?;
+    Ok(id)
+}
+
+#[pyfunction]
+pub fn trace_id_batch(py: Python<'_>, count: usize) -> PyResult<Vec<String>> {
+    let ids: Vec<String> = py.allow_threads(|| (0..count).into_par_iter().map(|_| new_trace_id()).collect());
+    audit::record(py, "trace_id", &ids)?;
+    Ok(ids)
+}
+
+#[pyfunction]
+pub fn span_id_batch(py: Python<'_>, count: usize) -> PyResult<Vec<String>> {
+    let ids: Vec<String> = py.allow_threads(|| (0..count).into_par_iter().map(|_| new_span_id()).collect());
+    audit::record(py, "span_id", &ids)?;
+    Ok(ids)
+}
+
+/// OpenTelemetry `TraceIdRatioBased` decision for `trace_id`: the low 8
+/// bytes, big-endian and shifted right by one, must fall below
+/// `ratio * 2**63`, as in the reference SDKs, so the same trace is sampled
+/// here and in a collector configured with the same ratio. `trace_id` may be
+/// the 32-digit hex string, 16 bytes, a UUID or an int; a ratio of 1 or more
+/// samples everything and 0 or less nothing.
+#[pyfunction]
+pub fn should_sample(trace_id: &Bound<'_, PyAny>, ratio: f64) -> PyResult<bool> {
+    if ratio.is_nan() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("ratio must be a number"));
+    }
+    let id = match trace_id.extract::<u128>() {
+        Ok(id) => id,
+        Err(_) => u128::from_be_bytes(crate::uuid_bytes(trace_id)?),
+    };
+    if ratio >= 1.0 {
+        return Ok(true);
+    }
+    let bound = (ratio.max(0.0) * (1u64 << 63) as f64) as u64;
+    Ok((id as u64) >> 1 < bound)
+}
@@ -0,0 +1,133 @@
+//! TypeIDs: a lowercase type prefix, an underscore, and a UUID (v7 when
+//! generated) in 26 characters of lowercase Crockford base32, such as
+//! `user_01h455vb4pex5vsknk084sn02q`.
+
+use pyo3::prelude::*;
+
+use crate::audit;
+use crate::encoding::encode_u128;
+use crate::hashing;
+use crate::keys::fnv1a;
+use crate::UUID;
+
+/// Lowercase Crockford base32; TypeID suffixes allow no other symbols.
+const SUFFIX_ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+const SUFFIX_LEN: usize = 26;
+const MAX_PREFIX_LEN: usize = 63;
+
+fn invalid(message: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(message)
+}
+
+/// Prefixes are up to 63 characters of `a-z` and `_`, not starting or ending
+/// with `_`; the empty prefix is allowed.
+fn check_prefix(prefix: &str) -> PyResult<()> {
+    if prefix.len() > MAX_PREFIX_LEN {
+        return Err(invalid(format!("TypeID prefix is longer than {} characters: {:?}", MAX_PREFIX_LEN, prefix)));
+    }
+    if !prefix.bytes().all(|c| c.is_ascii_lowercase() || c == b'_') || prefix.starts_with('_') || prefix.ends_with('_') {
+        return Err(invalid(format!("Invalid TypeID prefix: {:?}", prefix)));
+    }
+    Ok(())
+}
+
+fn decode_suffix(suffix: &str) -> PyResult<[u8; 16]> {
+    let error = || invalid(format!("Invalid TypeID suffix: {:?}", suffix));
+    if suffix.len() != SUFFIX_LEN || suffix.as_bytes()[0] > b'7' {
+        return Err(error());
+    }
+    let mut value: u128 = 0;
+    for c in suffix.bytes() {
+        let digit = SUFFIX_ALPHABET.iter().position(|&a| a == c).ok_or_else(error)?;
+        value = value << 5 | digit as u128;
+    }
+    Ok(value.to_be_bytes())
+}
+
+#[pyclass(name = "TypeID", frozen, eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TypeId {
+    prefix: String,
+    pub(crate) bytes: [u8; 16],
+}
+
+#[allow(clippy::wrong_self_convention)]
+#[pymethods]
+impl TypeId {
+    /// TypeID with `prefix` for `id`, or for a new v7 UUID if `id` is omitted.
+    #[new]
+    #[pyo3(signature = (prefix="", id=None))]
+    pub(crate) fn new(prefix: &str, id: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        check_prefix(prefix)?;
+        let bytes = match id {
+            Some(id) => crate::uuid_bytes(id)?,
+            None => *uuid::Uuid::now_v7().as_bytes(),
+        };
+        Ok(TypeId { prefix: prefix.to_string(), bytes })
+    }
+
+    /// Parse `prefix_suffix`, or a bare suffix for the empty prefix.
+    #[staticmethod]
+    pub(crate) fn from_string(s: &str) -> PyResult<TypeId> {
+        let (prefix, suffix) = match s.rsplit_once('_') {
+            Some(("", _)) => return Err(invalid(format!("TypeID has an empty prefix before '_': {:?}", s))),
+            Some((prefix, suffix)) => (prefix, suffix),
+            None => ("", s),
+        };
+        check_prefix(prefix)?;
+        Ok(TypeId { prefix: prefix.to_string(), bytes: decode_suffix(suffix)? })
+    }
+
+    #[staticmethod]
+    fn from_uuid(prefix: &str, id: &Bound<'_, PyAny>) -> PyResult<TypeId> {
+        TypeId::new(prefix, Some(id))
+    }
+
+    /// Whether `s` is a valid TypeID, optionally with exactly `prefix`.
+    #[staticmethod]
+    #[pyo3(signature = (s, prefix=None))]
+    fn is_valid(s: &str, prefix: Option<&str>) -> bool {
+        TypeId::from_string(s).is_ok_and(|id| prefix.is_none_or(|p| p == id.prefix))
+    }
+
+    #[getter]
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    #[getter]
+    fn suffix(&self) -> String {
+        encode_u128(u128::from_be_bytes(self.bytes), SUFFIX_ALPHABET)
+    }
+
+    fn to_uuid(&self) -> UUID {
+        UUID { bytes: self.bytes }
+    }
+
+    pub(crate) fn __str__(&self) -> String {
+        if self.prefix.is_empty() {
+            self.suffix()
+        } else {
+            format!("{}_{}", self.prefix, self.suffix())
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TypeID('{}')", self.__str__())
+    }
+
+    /// `hashing::fold128` of the 128 bits mixed with an FNV-1a hash of the
+    /// prefix, stable across processes.
+    fn __hash__(&self) -> isize {
+        hashing::mix64(hashing::fold128(&self.bytes) ^ fnv1a(self.prefix.as_bytes())) as isize
+    }
+}
+
+/// New TypeID for `prefix` backed by a v7 UUID.
+#[pyfunction]
+#[pyo3(signature = (prefix=""))]
+pub fn typeid(py: Python<'_>, prefix: &str) -> PyResult<TypeId> {
+    let id = TypeId::new(prefix, None)?;
+    audit::record(py, "typeid", [id.clone()])?;
+    Ok(id)
+}
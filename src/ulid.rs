@@ -0,0 +1,317 @@
+//! ULIDs: 128-bit IDs written as 26 Crockford base32 characters, with a
+//! 48-bit millisecond timestamp followed by 80 random bits.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDateTime, PyDict, PyString, PyTzInfo};
+use rayon::prelude::*;
+
+use crate::alphabets::CROCKFORD32;
+use crate::audit;
+use crate::encoding::{crockford_digit, encode_u128};
+use crate::hashing;
+use crate::monotonic::{now_ms, Overflow};
+use crate::parse::parse_lenient;
+use crate::{canonical_string, dedupe, UUID};
+
+/// Characters in an encoded ULID.
+const ULID_LEN: usize = 26;
+
+const RANDOM_MASK: u128 = (1 << 80) - 1;
+
+pub(crate) fn ulid_string(bytes: &[u8; 16]) -> String {
+    encode_u128(u128::from_be_bytes(*bytes), CROCKFORD32.as_bytes())
+}
+
+/// Parse a ULID string, case-insensitively and with Crockford's `I`/`L`/`O`
+/// aliases. The first character must be at most `7`, or the value would not
+/// fit in 128 bits.
+pub(crate) fn parse_ulid(s: &[u8]) -> Result<[u8; 16], &'static str> {
+    if s.len() != ULID_LEN {
+        return Err("expected 26 characters");
+    }
+    let mut value: u128 = 0;
+    for &c in s {
+        value = value << 5 | crockford_digit(c).ok_or("invalid Crockford base32 character")? as u128;
+    }
+    if crockford_digit(s[0]).is_some_and(|d| d > 7) {
+        return Err("value exceeds 128 bits");
+    }
+    Ok(value.to_be_bytes())
+}
+
+fn convert_batch(
+    py: Python<'_>,
+    values: Vec<String>,
+    what: &str,
+    convert: impl Fn(&[u8]) -> Result<String, &'static str> + Sync + Send,
+) -> PyResult<Vec<String>> {
+    let converted: Vec<Result<String, &'static str>> =
+        py.allow_threads(|| values.par_iter().map(|s| convert(s.as_bytes())).collect());
+    converted
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            result.map_err(|reason| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                    "Invalid {} at index {}: {:?} ({})",
+                    what, i, values[i], reason
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Canonical UUID string with the same 128 bits as the ULID string `s`.
+#[pyfunction]
+pub fn ulid_str_to_uuid_str(s: &str) -> PyResult<String> {
+    let bytes = parse_ulid(s.as_bytes())
+        .map_err(|reason| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid Ulid {:?}: {}", s, reason)))?;
+    Ok(canonical_string(&bytes))
+}
+
+/// ULID string with the same 128 bits as the UUID string `s`, which may be in
+/// any form `normalize_batch` accepts.
+#[pyfunction]
+pub fn uuid_str_to_ulid_str(s: &str) -> PyResult<String> {
+    let bytes = parse_lenient(s.as_bytes())
+        .map_err(|reason| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid UUID {:?}: {}", s, reason)))?;
+    Ok(ulid_string(&bytes))
+}
+
+/// `ulid_str_to_uuid_str` over a list in parallel; an invalid entry raises
+/// `ValueError` naming its index.
+#[pyfunction]
+pub fn ulid_str_to_uuid_str_batch(py: Python<'_>, values: Vec<String>) -> PyResult<Vec<String>> {
+    convert_batch(py, values, "ULID", |s| parse_ulid(s).map(|bytes| canonical_string(&bytes)))
+}
+
+/// `uuid_str_to_ulid_str` over a list in parallel; an invalid entry raises
+/// `ValueError` naming its index.
+#[pyfunction]
+pub fn uuid_str_to_ulid_str_batch(py: Python<'_>, values: Vec<String>) -> PyResult<Vec<String>> {
+    convert_batch(py, values, "UUID", |s| parse_lenient(s).map(|bytes| ulid_string(&bytes)))
+}
+
+/// ULID bytes for `ms` with the given 80 random bits; `reverse` inverts the
+/// timestamp so that newer ULIDs sort first.
+fn encode_ulid(ms: u64, random: u128, reverse: bool) -> [u8; 16] {
+    let ms = if reverse { !ms } else { ms } & 0xffff_ffff_ffff;
+    ((ms as u128) << 80 | random & RANDOM_MASK).to_be_bytes()
+}
+
+/// ULID bytes for the current time.
+pub(crate) fn ulid_bytes(reverse: bool) -> [u8; 16] {
+    encode_ulid(now_ms(), random80(), reverse)
+}
+
+fn random80() -> u128 {
+    fastrand::u128(..) & RANDOM_MASK
+}
+
+/// A ULID: 48-bit Unix millisecond timestamp and 80 random bits, ordered
+/// and compared by value.
+#[pyclass(name = "ULID", frozen, eq, ord)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid {
+    pub(crate) bytes: [u8; 16],
+}
+
+#[allow(clippy::wrong_self_convention)]
+#[pymethods]
+impl Ulid {
+    /// From a 26-character ULID string, 16 bytes, or a `UUID`.
+    #[new]
+    fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if let Ok(s) = value.downcast::<PyString>() {
+            let s = s.to_str()?;
+            let bytes = parse_ulid(s.as_bytes())
+                .map_err(|reason| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid Ulid {:?}: {}", s, reason)))?;
+            return Ok(Ulid { bytes });
+        }
+        if let Ok(b) = value.downcast::<PyBytes>() {
+            let bytes = b
+                .as_bytes()
+                .try_into()
+                .map_err(|_| PyErr::new::<pyo3::exceptions::PyValueError, _>("ULID takes exactly 16 bytes"))?;
+            return Ok(Ulid { bytes });
+        }
+        if let Ok(id) = value.downcast::<UUID>() {
+            return Ok(Ulid { bytes: id.get().bytes });
+        }
+        Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("Expected a ULID string, 16 bytes, or a UUID"))
+    }
+
+    #[getter]
+    fn bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.bytes)
+    }
+
+    #[getter]
+    fn int(&self) -> u128 {
+        u128::from_be_bytes(self.bytes)
+    }
+
+    #[getter]
+    fn timestamp_ms(&self) -> u64 {
+        u64::from_be_bytes([0, 0, self.bytes[0], self.bytes[1], self.bytes[2], self.bytes[3], self.bytes[4], self.bytes[5]])
+    }
+
+    /// Creation time as an aware UTC `datetime`.
+    #[getter]
+    fn datetime<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDateTime>> {
+        let utc = PyTzInfo::utc(py)?;
+        PyDateTime::from_timestamp(py, self.timestamp_ms() as f64 / 1000.0, Some(&utc))
+    }
+
+    /// The same 128 bits as a `UUID`.
+    fn to_uuid(&self) -> UUID {
+        UUID { bytes: self.bytes }
+    }
+
+    #[staticmethod]
+    fn from_uuid(id: &Bound<'_, PyAny>) -> PyResult<Ulid> {
+        Ok(Ulid { bytes: crate::uuid_bytes(id)? })
+    }
+
+    fn __str__(&self) -> String {
+        ulid_string(&self.bytes)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ULID('{}')", ulid_string(&self.bytes))
+    }
+
+    /// `hashing::fold128` of the 128 bits, stable across processes.
+    fn __hash__(&self) -> isize {
+        hashing::fold128(&self.bytes) as isize
+    }
+}
+
+/// New ULID from the current time; with `reverse=True` the timestamp bits
+/// are inverted so that newer ULIDs sort first.
+#[pyfunction]
+#[pyo3(signature = (reverse=false))]
+pub fn ulid(py: Python<'_>, reverse: bool) -> PyResult<Ulid> {
+    let id = Ulid { bytes: ulid_bytes(reverse) };
+    audit::record(py, "ulid", [id])?;
+    Ok(id)
+}
+
+/// `count` independent ULIDs; use `MonotonicUlidFactory` when IDs from the
+/// same millisecond must also sort in generation order.
+#[pyfunction]
+#[pyo3(signature = (count, reverse=false, unique=false))]
+pub fn ulid_batch(py: Python<'_>, count: usize, reverse: bool, unique: bool) -> PyResult<Vec<Ulid>> {
+    let ms = now_ms();
+    let ids: Vec<Ulid> = py.allow_threads(|| {
+        let generate = || Ulid { bytes: encode_ulid(ms, random80(), reverse) };
+        let mut ids: Vec<Ulid> = (0..count).into_par_iter().map(|_| generate()).collect();
+        if unique {
+            dedupe(&mut ids, generate);
+        }
+        ids
+    });
+    audit::record(py, "ulid", ids.iter().copied())?;
+    Ok(ids)
+}
+
+/// Strictly increasing ULIDs (the ULID spec's monotonic mode): within one
+/// millisecond, and when the clock steps back, each ULID increments the
+/// random part of the previous one.
+///
+/// If the 80 random bits overflow, `overflow="borrow"` (the default) moves on
+/// to the next millisecond, `"wait"` sleeps until the clock gets there, and
+/// `"raise"` raises `OverflowError` as the ULID spec suggests.
+#[pyclass]
+pub struct MonotonicUlidFactory {
+    counter: UlidCounter,
+    overflow: Overflow,
+}
+
+/// Monotonic ULID source: the millisecond and 80 random bits of the last ULID.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct UlidCounter {
+    last: Option<(u64, u128)>,
+}
+
+impl UlidCounter {
+    /// Rebuild the counter from the last ULID that was handed out, so the
+    /// next value sorts strictly after it.
+    pub(crate) fn resume(last: &[u8; 16]) -> Self {
+        let value = u128::from_be_bytes(*last);
+        UlidCounter { last: Some(((value >> 80) as u64, value & RANDOM_MASK)) }
+    }
+
+    /// Next ULID, handling exhausted random bits according to `overflow`.
+    pub(crate) fn next_with(&mut self, overflow: Overflow) -> PyResult<[u8; 16]> {
+        let (ms, random) = loop {
+            let now = now_ms();
+            match self.last {
+                Some((ms, random)) if now <= ms => {
+                    if random < RANDOM_MASK {
+                        break (ms, random + 1);
+                    }
+                    if overflow.on_exhausted()? {
+                        break (ms + 1, random80());
+                    }
+                }
+                _ => break (now, random80()),
+            }
+        };
+        self.last = Some((ms, random));
+        Ok(encode_ulid(ms, random, false))
+    }
+}
+
+#[pymethods]
+impl MonotonicUlidFactory {
+    #[new]
+    #[pyo3(signature = (overflow="borrow"))]
+    fn new(overflow: &str) -> PyResult<Self> {
+        Ok(MonotonicUlidFactory { counter: UlidCounter::default(), overflow: Overflow::parse(overflow)? })
+    }
+
+    fn __call__(&mut self, py: Python<'_>) -> PyResult<Ulid> {
+        let id = Ulid { bytes: py.allow_threads(|| self.counter.next_with(self.overflow))? };
+        audit::record(py, "ulid", [id])?;
+        Ok(id)
+    }
+
+    /// `count` consecutive ULIDs, in increasing order.
+    fn batch(&mut self, py: Python<'_>, count: usize) -> PyResult<Vec<Ulid>> {
+        let ids = py.allow_threads(|| {
+            (0..count)
+                .map(|_| Ok(Ulid { bytes: self.counter.next_with(self.overflow)? }))
+                .collect::<PyResult<Vec<Ulid>>>()
+        })?;
+        audit::record(py, "ulid", ids.iter().copied())?;
+        Ok(ids)
+    }
+
+    /// Checkpoint of the last ULID handed out; pass it to
+    /// `MonotonicUlidFactory.resume` so a restarted process continues after it.
+    fn state<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let state = PyDict::new(py);
+        let last = self.counter.last.map(|(ms, random)| ulid_string(&encode_ulid(ms, random, false)));
+        state.set_item("last", last)?;
+        state.set_item("overflow", self.overflow.name())?;
+        Ok(state)
+    }
+
+    #[staticmethod]
+    fn resume(state: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let invalid = |msg: &str| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid factory state: {}", msg));
+        let overflow = match state.get_item("overflow")? {
+            Some(value) => Overflow::parse(value.extract()?)?,
+            None => Overflow::default(),
+        };
+        let counter = match state.get_item("last")? {
+            Some(value) if !value.is_none() => {
+                let last: String = value.extract()?;
+                UlidCounter::resume(&parse_ulid(last.as_bytes()).map_err(invalid)?)
+            }
+            _ => UlidCounter::default(),
+        };
+        Ok(MonotonicUlidFactory { counter, overflow })
+    }
+}
@@ -0,0 +1,86 @@
+//! Golden test vectors for ports of rustid's encodings to other languages.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::hashing::mix64;
+use crate::{codename, UUID};
+
+/// Inputs that exercise padding, the top bit and single-bit carries.
+const EDGE_CASES: [u128; 8] = [
+    0,
+    1,
+    u64::MAX as u128,
+    1 << 64,
+    1 << 127,
+    u128::MAX - 1,
+    u128::MAX,
+    0x0123456789abcdef_fedcba9876543210,
+];
+
+/// SplitMix64 sequence; spelled out rather than taken from an RNG crate so
+/// that the vectors for a seed never change between releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        mix64(self.0)
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (self.next() as u128) << 64 | self.next() as u128
+    }
+}
+
+/// Set the version nibble and the RFC variant bits.
+fn with_version(value: u128, version: u128) -> u128 {
+    value & !(0xf << 76) & !(0b11 << 62) | version << 76 | 0b10 << 62
+}
+
+fn inputs(kind: &str, seed: u64, count: usize) -> PyResult<Vec<u128>> {
+    if kind == "edge" {
+        return Ok(EDGE_CASES.to_vec());
+    }
+    let mut rng = SplitMix64(seed);
+    let shape: fn(u128) -> u128 = match kind {
+        "random" => |value| value,
+        "uuid4" => |value| with_version(value, 4),
+        "uuid7" => |value| with_version(value, 7),
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported vector kind: {}", kind))),
+    };
+    Ok((0..count).map(|_| shape(rng.next_u128())).collect())
+}
+
+/// Deterministic test vectors: for each input ID, its encodings as rustid
+/// produces them, so ports in other languages can check byte compatibility.
+///
+/// `kind` shapes the inputs: `"uuid4"` and `"uuid7"` set those version and
+/// variant bits on pseudo-random values, `"random"` uses all 128 bits, and
+/// `"edge"` returns a fixed set of boundary values (ignoring `seed` and
+/// `count`). The same `kind`, `seed` and `count` always give the same
+/// vectors. Each vector is a dict with the input as 32 lowercase hex digits
+/// (`"bytes"`) and its `"canonical"`, `"base32"` (Crockford), `"base58"`,
+/// `"base36"`, `"slug"` (URL component), `"sortable"` (`short_id(full=True,
+/// sortable=True)`) and `"codename"` forms.
+#[pyfunction]
+#[pyo3(signature = (kind, seed=0, count=16))]
+pub fn vectors<'py>(py: Python<'py>, kind: &str, seed: u64, count: usize) -> PyResult<Vec<Bound<'py, PyDict>>> {
+    inputs(kind, seed, count)?
+        .into_iter()
+        .map(|value| {
+            let id = UUID { bytes: value.to_be_bytes() };
+            let vector = PyDict::new(py);
+            vector.set_item("bytes", hex::encode(id.bytes))?;
+            // Lowercase whatever `set_uppercase` says, so the vectors stay fixed.
+            vector.set_item("canonical", uuid::Uuid::from_bytes(id.bytes).hyphenated().to_string())?;
+            vector.set_item("base32", id.base32())?;
+            vector.set_item("base58", id.base58())?;
+            vector.set_item("base36", id.base36())?;
+            vector.set_item("slug", id.to_url_component())?;
+            vector.set_item("sortable", id.short_id(true, true))?;
+            vector.set_item("codename", codename::default_alias(&id.bytes))?;
+            Ok(vector)
+        })
+        .collect()
+}
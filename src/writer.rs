@@ -0,0 +1,212 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyInt};
+
+use crate::audit;
+use crate::columns::{parse_columns, ColumnKind};
+
+const WRITE_CHUNK: usize = 65536;
+
+/// Writer over a file descriptor owned by the caller; it is flushed but never closed.
+#[cfg(unix)]
+struct BorrowedFd(std::mem::ManuallyDrop<File>);
+
+#[cfg(unix)]
+impl Write for BorrowedFd {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// The file descriptor `path_or_fd` names: a non-negative `int`, or the
+/// `fileno()` of a file object; `None` for paths. `bool` is rejected rather
+/// than read as fd 0 or 1.
+fn output_fd(path_or_fd: &Bound<'_, PyAny>) -> PyResult<Option<i32>> {
+    if path_or_fd.is_instance_of::<PyBool>() {
+        return Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>("Expected a path or file descriptor, not bool"));
+    }
+    let fd = if path_or_fd.is_instance_of::<PyInt>() {
+        path_or_fd.extract::<i32>()?
+    } else if path_or_fd.hasattr("fileno")? {
+        path_or_fd.call_method0("fileno")?.extract::<i32>()?
+    } else {
+        return Ok(None);
+    };
+    if fd < 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid file descriptor: {}", fd)));
+    }
+    Ok(Some(fd))
+}
+
+/// Open a path (created/truncated) or wrap a file descriptor, given as an
+/// `int` or an object with `fileno()`.
+pub(crate) fn open_output(path_or_fd: &Bound<'_, PyAny>) -> PyResult<Box<dyn Write + Send>> {
+    if let Some(fd) = output_fd(path_or_fd)? {
+        #[cfg(unix)]
+        {
+            use std::os::fd::FromRawFd;
+            let file = unsafe { File::from_raw_fd(fd) };
+            return Ok(Box::new(BorrowedFd(std::mem::ManuallyDrop::new(file))));
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = fd;
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("File descriptors are only supported on Unix"));
+        }
+    }
+    let path: PathBuf = path_or_fd.extract()?;
+    Ok(Box::new(File::create(path)?))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CopyFormat {
+    Text,
+    Csv,
+}
+
+fn write_rows(
+    out: Box<dyn Write + Send>,
+    count: usize,
+    columns: &[(String, ColumnKind)],
+    format: CopyFormat,
+    header: bool,
+) -> PyResult<()> {
+    let mut out = BufWriter::new(out);
+    let sep: &[u8] = match format {
+        CopyFormat::Text => b"\t",
+        CopyFormat::Csv => b",",
+    };
+    if header {
+        let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        out.write_all(names.join(",").as_bytes())?;
+        out.write_all(b"\n")?;
+    }
+    let mut written = 0;
+    while written < count {
+        let n = WRITE_CHUNK.min(count - written);
+        let values: Vec<Vec<String>> = columns.iter().map(|(_, kind)| kind.generate(n)).collect();
+        for ((_, kind), column) in columns.iter().zip(&values) {
+            audit::record_detached(kind.name(), column)?;
+        }
+        for row in 0..n {
+            for (i, column) in values.iter().enumerate() {
+                if i > 0 {
+                    out.write_all(sep)?;
+                }
+                out.write_all(column[row].as_bytes())?;
+            }
+            out.write_all(b"\n")?;
+        }
+        written += n;
+    }
+    Ok(out.flush()?)
+}
+
+/// Write `count` generated rows as PostgreSQL COPY text (tab separated) or CSV.
+/// `path_or_fd` is a path, a file descriptor, or a file object whose
+/// `fileno()` is written to directly; flush any buffered data first.
+/// Generated values and the `[A-Za-z0-9_]` column names and prefixes never
+/// contain separators, quotes, or backslashes, so no escaping is needed.
+#[pyfunction]
+#[pyo3(signature = (path_or_fd, count, columns=None, format="text", header=false))]
+pub fn write_copy(
+    py: Python<'_>,
+    path_or_fd: &Bound<'_, PyAny>,
+    count: usize,
+    columns: Option<Vec<(String, String)>>,
+    format: &str,
+    header: bool,
+) -> PyResult<usize> {
+    let format = match format {
+        "text" => CopyFormat::Text,
+        "csv" => CopyFormat::Csv,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported format: {}", format))),
+    };
+    if header && format == CopyFormat::Text {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("COPY text format has no header row"));
+    }
+    let columns = parse_columns(columns.unwrap_or_else(|| vec![("id".to_string(), "uuid7".to_string())]))?;
+    let out = open_output(path_or_fd)?;
+    py.allow_threads(|| write_rows(out, count, &columns, format, header))?;
+    Ok(count)
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet_file(
+    file: File,
+    count: usize,
+    kind: &ColumnKind,
+    column: &str,
+    binary: bool,
+    row_group_size: usize,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, FixedSizeBinaryArray, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+
+    let data_type = if binary { DataType::FixedSizeBinary(16) } else { DataType::Utf8 };
+    let schema = Arc::new(Schema::new(vec![Field::new(column, data_type, false)]));
+    let props = WriterProperties::builder().set_max_row_group_row_count(Some(row_group_size)).build();
+    let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props))?;
+    let mut written = 0;
+    while written < count {
+        let n = row_group_size.min(count - written);
+        let array: ArrayRef = match kind.generate_bytes(n) {
+            Some(ids) if binary => {
+                audit::record_detached(kind.name(), ids.iter().map(|&bytes| crate::UUID { bytes }))?;
+                Arc::new(FixedSizeBinaryArray::try_from_iter(ids.into_iter())?)
+            }
+            _ => {
+                let values = kind.generate(n);
+                audit::record_detached(kind.name(), &values)?;
+                Arc::new(StringArray::from(values))
+            }
+        };
+        writer.write(&RecordBatch::try_new(schema.clone(), vec![array])?)?;
+        written += n;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+/// Write `count` generated IDs to a single-column Parquet file.
+#[cfg(feature = "parquet")]
+#[pyfunction]
+#[pyo3(signature = (path, count, kind="uuid7", column="id", format="string", row_group_size=None))]
+pub fn write_parquet(
+    py: Python<'_>,
+    path: PathBuf,
+    count: usize,
+    kind: &str,
+    column: &str,
+    format: &str,
+    row_group_size: Option<usize>,
+) -> PyResult<usize> {
+    let kind = ColumnKind::parse(kind)?;
+    let binary = match format {
+        "string" => false,
+        "binary" if kind.generate_bytes(0).is_some() => true,
+        "binary" => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Binary format requires a UUID kind")),
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unsupported format: {}", format))),
+    };
+    let file = File::create(path)?;
+    let row_group_size = row_group_size.unwrap_or(1 << 20).max(1);
+    py.allow_threads(|| write_parquet_file(file, count, &kind, column, binary, row_group_size)).map_err(|e| {
+        // Exceptions from the audit hook pass through unchanged.
+        match e.downcast::<PyErr>() {
+            Ok(e) => *e,
+            Err(e) => PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()),
+        }
+    })?;
+    Ok(count)
+}